@@ -0,0 +1,74 @@
+//! Times validation of expecting components whose expected components use
+//! table storage, sparse-set storage, or a mix of both.
+//!
+//! Run with `cargo bench --bench presence`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bevy_ecs::component::Component;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_ecs::world::World;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::validation_system;
+
+const ENTITIES: usize = 10_000;
+const ITERATIONS: u32 = 20;
+
+#[derive(Component, Default)]
+struct TableA;
+
+#[derive(Component, Default)]
+struct TableB;
+
+#[derive(Component, Default)]
+#[component(storage = "SparseSet")]
+struct SparseA;
+
+#[derive(Component, Default)]
+#[component(storage = "SparseSet")]
+struct SparseB;
+
+#[derive(Component, ExpectComponents)]
+#[expects(TableA, TableB)]
+struct ExpectsTable;
+
+#[derive(Component, ExpectComponents)]
+#[expects(SparseA, SparseB)]
+struct ExpectsSparse;
+
+#[derive(Component, ExpectComponents)]
+#[expects(TableA, SparseA)]
+struct ExpectsMixed;
+
+fn time_validation(label: &str, spawn: impl Fn(&mut World)) {
+    let mut world = World::new();
+    for _ in 0..ENTITIES {
+        spawn(&mut world);
+    }
+
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let violations = world.run_system_once(validation_system).unwrap();
+        total += start.elapsed();
+        assert!(black_box(violations).is_empty());
+    }
+
+    println!(
+        "{label:>8}: {:?} per pass of {ENTITIES} entities",
+        total / ITERATIONS
+    );
+}
+
+fn main() {
+    time_validation("table", |world| {
+        world.spawn((ExpectsTable, TableA, TableB));
+    });
+    time_validation("sparse", |world| {
+        world.spawn((ExpectsSparse, SparseA, SparseB));
+    });
+    time_validation("mixed", |world| {
+        world.spawn((ExpectsMixed, TableA, SparseA));
+    });
+}
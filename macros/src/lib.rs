@@ -4,10 +4,14 @@
 //! depend on this crate directly; instead use `bevy_expected_components` which
 //! re-exports the macro.
 
+use std::collections::HashSet;
+
 use proc_macro::TokenStream;
-use quote::quote;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{parse_macro_input, DeriveInput, Path, Token};
+use syn::{parse_macro_input, Attribute, Data, DataEnum, DeriveInput, LitInt, LitStr, Path, Token};
 
 /// Derive macro for generating `ExpectComponents` implementation.
 ///
@@ -25,6 +29,10 @@ use syn::{parse_macro_input, DeriveInput, Path, Token};
 /// struct PhysicsBody;
 /// ```
 ///
+/// The attribute is `expects`, not `expect`: `#[expect(...)]` is Rust's
+/// built-in lint attribute and keeps that meaning on derived components. Naming
+/// a component in it is a compile error pointing at `#[expects]`.
+///
 /// # Multiple Attributes
 ///
 /// You can use multiple `#[expects(...)]` attributes:
@@ -36,6 +44,311 @@ use syn::{parse_macro_input, DeriveInput, Path, Token};
 /// struct PhysicsBody;
 /// ```
 ///
+/// # Unless Present
+///
+/// `#[expect_unless_present(Static, Velocity)]` requires `Velocity` only on
+/// entities that do not have `Static`:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_unless_present(Static, Velocity)]
+/// struct Body;
+/// ```
+///
+/// # Conditional Expectations
+///
+/// `#[expect_if(PhysicsEnabled, RigidBody)]` requires `RigidBody` only on
+/// entities that also have `PhysicsEnabled`:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_if(PhysicsEnabled, RigidBody)]
+/// struct Ragdoll;
+/// ```
+///
+/// # Alternatives
+///
+/// `#[expect_any(Mesh2d, Sprite)]` requires at least one of the listed
+/// components, for components that work with any of several others:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_any(Mesh2d, Sprite)]
+/// struct Renderer;
+/// ```
+///
+/// `#[expect_one_of(TeamRed, TeamBlue)]` requires exactly one of the listed
+/// components, and fails if none or several are present:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_one_of(TeamRed, TeamBlue, TeamGreen)]
+/// struct Player;
+/// ```
+///
+/// # Forbidden Components
+///
+/// `#[forbids(Velocity)]` fails if `Velocity` is on the entity when this
+/// component is added, for components that must never coexist. The attribute
+/// is `forbids` because `forbid` is a built-in lint attribute:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[forbids(Velocity)]
+/// struct Static;
+/// ```
+///
+/// # Resources
+///
+/// `#[expect_resource(GridConfig)]` fails if the `GridConfig` resource does not
+/// exist when this component is added:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_resource(GridConfig)]
+/// struct GridCell;
+/// ```
+///
+/// # Non-Default Values
+///
+/// `#[expect_non_default(Config)]` expects `Config` to be present and to differ
+/// from `Config::default()`, catching components that were added but never
+/// configured. The component must implement `Default` and `PartialEq`:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_non_default(Config)]
+/// struct Server;
+/// ```
+///
+/// # Enum Variants
+///
+/// On an enum component, `#[expects(...)]` on a variant applies only while the
+/// component is that variant:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// enum AiState {
+///     Idle,
+///     #[expects(Target)]
+///     Chasing,
+///     #[expects(PatrolRoute)]
+///     Patrolling,
+/// }
+/// ```
+///
+/// # Predicates
+///
+/// `#[expect_where(Health, |health: &Health| health.0 > 0.0)]` expects `Health`
+/// to be present and its value to pass the predicate when this component is
+/// added, and again whenever `Health` is mutated in place. The predicate is a closure that captures nothing, or the path of a
+/// function, taking `&Health` and returning `bool`:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_where(Health, |health: &Health| health.0 > 0.0)]
+/// struct Player;
+/// ```
+///
+/// # Relationships
+///
+/// `#[expect_both_ends(LinkedTo, Synced)]` requires `Synced` on both the entity
+/// and the target of its `LinkedTo` relationship:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_both_ends(LinkedTo, Synced)]
+/// struct Mirror;
+/// ```
+///
+/// `#[expect_on_related(EquippedBy, Hand)]` requires `Hand` on the target of the
+/// entity's `EquippedBy` relationship, but not on the entity itself:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_on_related(EquippedBy, Hand)]
+/// struct Sword;
+/// ```
+///
+/// # Incoming Relationships
+///
+/// `#[expect_incoming_relations(ConnectedTo, min = 2)]` requires at least two
+/// entities to point at this one through `ConnectedTo`. Since incoming links are
+/// usually added after the target spawns, this is checked once per frame:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_incoming_relations(ConnectedTo, min = 2)]
+/// struct Hub;
+/// ```
+///
+/// # Children
+///
+/// `#[expect_all_children(Transform)]` requires every direct child of the entity
+/// to have `Transform`. Children are usually attached after the parent spawns,
+/// so this is checked once per frame:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_all_children(Transform)]
+/// struct PhysicsWorld;
+/// ```
+///
+/// `#[expect_children(Slot, min = 9, max = 12)]` requires the number of direct
+/// children with `Slot` to lie within the bounds. Either bound may be left out:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_children(Slot, min = 9)]
+/// struct Hotbar;
+/// ```
+///
+/// `#[expect_on_parent(ListContainer)]` requires the entity's parent to have
+/// `ListContainer`, and fails if the entity has no parent:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_on_parent(ListContainer)]
+/// struct ListItem;
+/// ```
+///
+/// # Required Components
+///
+/// `#[expect_include_required]` lets components from the type's own
+/// `#[require(...)]` closure satisfy its expectations, even if they are later
+/// removed from the entity:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[require(Velocity)]
+/// #[expects(Velocity)]
+/// #[expect_include_required]
+/// struct Thruster;
+/// ```
+///
+/// # Composing Expectations
+///
+/// `#[expect_include(VehicleExpectations)]` merges in the expected components of
+/// another `ExpectComponents` type, so shared expectations are declared once:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expects(Transform)]
+/// struct VehicleExpectations;
+///
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_include(VehicleExpectations)]
+/// #[expects(Velocity)]
+/// struct Car;
+/// ```
+///
+/// # Computed Expectations
+///
+/// `#[expect_fn(compute_expected)]` appends the `TypeId`s returned by a
+/// `fn() -> &'static [TypeId]`, for expected sets a static attribute list can't
+/// express. The function is called once, on first use. Since only `TypeId`s are
+/// available, these components are named as registered in the world, or by
+/// `TypeId` if they were never registered:
+///
+/// ```rust,ignore
+/// fn compute_expected() -> &'static [TypeId] {
+///     static IDS: [TypeId; 1] = [TypeId::of::<Transform>()];
+///     &IDS
+/// }
+///
+/// #[derive(Component, ExpectComponents)]
+/// #[expect_fn(compute_expected)]
+/// struct Procedural;
+/// ```
+///
+/// # Strict Ordering
+///
+/// Add `require_prior` to demand that expected components were added in an
+/// earlier operation, not in the same spawn or insert:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expects(Transform, require_prior)]
+/// struct Attachment;
+/// ```
+///
+/// # Stability
+///
+/// Library authors can mark how settled the expectations in an attribute are
+/// with `stability = "stable"` or `stability = "experimental"`. The flag is
+/// recorded in the registry for tooling and does not change validation:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expects(Transform, stability = "stable")]
+/// #[expects(NetworkId, stability = "experimental")]
+/// struct Replicated;
+/// ```
+///
+/// # Per-Expectation Failure Modes
+///
+/// Override the global `FailureMode` for individual expectations with
+/// `mode = "panic" | "warn" | "warn_once" | "log" | "silent"`. `#[expect_warn(...)]` is
+/// shorthand for `mode = "warn"`:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expects(Collider, mode = "panic")]
+/// #[expect_warn(AudioSource)]
+/// struct Player;
+/// ```
+///
+/// `level = panic | error | warn` names the same choice by severity: `panic`
+/// panics, `error` logs an error and `warn` logs a warning:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expects(Transform)]
+/// #[expects(level = warn, Name)]
+/// struct Npc;
+/// ```
+///
+/// # Custom Messages
+///
+/// `message = "..."` adds context to the failure message of an expectation.
+/// `{expecting}`, `{missing}` and `{entity}` are replaced with the expecting
+/// component, the missing component and the entity:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expects(Transform, message = "did you forget to call spawn_enemy()?")]
+/// struct Enemy;
+/// ```
+///
+/// # Tags
+///
+/// Tag expectations with `tags = [...]` to group them across types. When an
+/// `EnabledTags` resource is present, only expectations with an enabled tag are
+/// enforced:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expects(Collider, tags = ["physics"])]
+/// #[expects(Sprite, tags = ["rendering"])]
+/// struct Player;
+/// ```
+///
+/// `group = "..."` adds a tag too, and names a group of expectations that the
+/// `ExpectationGroups` resource can disable together:
+///
+/// ```rust,ignore
+/// #[derive(Component, ExpectComponents)]
+/// #[expects(RigidBody, Collider, group = "physics")]
+/// struct Crate;
+/// ```
+///
+/// # Debug Assertions
+///
+/// The derive also generates a free function named after the type, such as
+/// `debug_assert_physics_body_expectations(entity: &EntityRef)`, which panics if
+/// the entity fails the type's expectations. It compiles to nothing in release
+/// builds and works without the plugin.
+///
 /// # Qualified Paths
 ///
 /// Full paths are supported:
@@ -45,62 +358,1353 @@ use syn::{parse_macro_input, DeriveInput, Path, Token};
 /// #[expects(bevy::transform::components::Transform)]
 /// struct MyComponent;
 /// ```
-#[proc_macro_derive(ExpectComponents, attributes(expects))]
+#[proc_macro_derive(
+    ExpectComponents,
+    attributes(
+        expects,
+        expect_unless_present,
+        expect_if,
+        expect_both_ends,
+        expect_on_related,
+        expect_incoming_relations,
+        expect_include_required,
+        expect_include,
+        expect_non_default,
+        expect_where,
+        expect_all_children,
+        expect_children,
+        expect_on_parent,
+        expect_fn,
+        expect_warn,
+        expect_any,
+        expect_one_of,
+        expect_resource,
+        forbids
+    )
+)]
 pub fn derive_expect_components(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
 
-    // Extract component paths from all #[expects(...)] attributes
-    let expected: Vec<Path> = input
-        .attrs
-        .iter()
-        .filter(|attr| attr.path().is_ident("expects"))
-        .flat_map(|attr| {
-            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
-                .unwrap_or_default()
-        })
-        .collect();
+    match expand(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
 
-    if expected.is_empty() {
-        return syn::Error::new_spanned(
-            &input.ident,
-            "ExpectComponents derive requires at least one #[expects(Component)] attribute",
-        )
-        .to_compile_error()
-        .into();
+/// Registers each listed expecting type without `inventory`.
+///
+/// Calls the `__register_<type>` function `#[derive(ExpectComponents)]`
+/// generates next to each type. Invoke it once in `main`, before the plugin is
+/// added, on platforms where `inventory` collects nothing, such as some WASM
+/// toolchains. Types `inventory` already collected are skipped.
+///
+/// ```rust,ignore
+/// fn main() {
+///     register_all_expectations!(PhysicsBody, vehicles::Vehicle);
+///     App::new().add_plugins(ExpectedComponentsPlugin).run();
+/// }
+/// ```
+#[proc_macro]
+pub fn register_all_expectations(input: TokenStream) -> TokenStream {
+    let types = parse_macro_input!(input with Punctuated::<Path, Token![,]>::parse_terminated);
+
+    match types
+        .into_iter()
+        .map(registrar_path)
+        .collect::<syn::Result<Vec<_>>>()
+    {
+        Ok(registrars) => quote! { { #(#registrars();)* } }.into(),
+        Err(err) => err.to_compile_error().into(),
     }
+}
 
-    // Generate TypeId expressions for each expected component
-    let type_ids = expected.iter().map(|p| {
-        quote! { ::std::any::TypeId::of::<#p>() }
-    });
+/// Expectations collected from a type's attributes.
+#[derive(Default)]
+struct Expectations {
+    expected: Vec<Path>,
+    prior: Vec<Path>,
+    unless_present: Vec<(Path, Path)>,
+    conditional: Vec<(Path, Path)>,
+    both_ends: Vec<(Path, Path)>,
+    on_related: Vec<(Path, Path)>,
+    incoming: Vec<IncomingArgs>,
+    include_required: bool,
+    includes: Vec<Path>,
+    non_default: Vec<Path>,
+    predicates: Vec<PredicateArgs>,
+    variants: Vec<(syn::Ident, Path)>,
+    all_children: Vec<Path>,
+    child_counts: Vec<ChildCountArgs>,
+    on_parent: Vec<Path>,
+    expected_fns: Vec<Path>,
+    stability: Vec<(Path, TokenStream2)>,
+    tags: Vec<(Path, Vec<LitStr>)>,
+    modes: Vec<(Path, TokenStream2)>,
+    messages: Vec<(Path, LitStr)>,
+    forbidden: Vec<Path>,
+    resources: Vec<Path>,
+    any_of: Vec<Vec<Path>>,
+    one_of: Vec<Vec<Path>>,
+}
 
-    // Generate type name expressions for error messages
-    let type_names = expected.iter().map(|p| {
-        quote! { ::std::any::type_name::<#p>() }
-    });
+impl Expectations {
+    fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut expectations = Self::default();
 
-    let expanded = quote! {
-        impl ::bevy_expected_components::ExpectComponents for #name {
+        for attr in attrs {
+            if attr.path().is_ident("expects") {
+                expectations.add_expects(attr.parse_args::<ExpectsArgs>()?);
+            } else if attr.path().is_ident("expect_unless_present") {
+                expectations.unless_present.push(parse_path_pair(attr)?);
+            } else if attr.path().is_ident("expect_if") {
+                expectations.conditional.push(parse_path_pair(attr)?);
+            } else if attr.path().is_ident("expect_both_ends") {
+                expectations.both_ends.push(parse_path_pair(attr)?);
+            } else if attr.path().is_ident("expect_on_related") {
+                expectations.on_related.push(parse_path_pair(attr)?);
+            } else if attr.path().is_ident("expect_incoming_relations") {
+                expectations
+                    .incoming
+                    .push(attr.parse_args::<IncomingArgs>()?);
+            } else if attr.path().is_ident("expect_include_required") {
+                attr.meta.require_path_only()?;
+                expectations.include_required = true;
+            } else if attr.path().is_ident("expect_non_default") {
+                let paths =
+                    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                expectations.expected.extend(paths.iter().cloned());
+                expectations.non_default.extend(paths);
+            } else if attr.path().is_ident("expect_where") {
+                let args = attr.parse_args::<PredicateArgs>()?;
+                expectations.expected.push(args.component.clone());
+                expectations.predicates.push(args);
+            } else if attr.path().is_ident("expect_children") {
+                expectations
+                    .child_counts
+                    .push(attr.parse_args::<ChildCountArgs>()?);
+            } else if attr.path().is_ident("expect_on_parent") {
+                let paths =
+                    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                expectations.on_parent.extend(paths);
+            } else if attr.path().is_ident("expect_all_children") {
+                let paths =
+                    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                expectations.all_children.extend(paths);
+            } else if attr.path().is_ident("expect_warn") {
+                let paths =
+                    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                expectations
+                    .modes
+                    .extend(paths.iter().map(|path| (path.clone(), quote! { Warn })));
+                expectations.expected.extend(paths);
+            } else if attr.path().is_ident("expect_any") {
+                let paths =
+                    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                expectations.any_of.push(paths.into_iter().collect());
+            } else if attr.path().is_ident("expect_one_of") {
+                let paths =
+                    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                expectations.one_of.push(paths.into_iter().collect());
+            } else if attr.path().is_ident("expect_resource") {
+                let paths =
+                    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                expectations.resources.extend(paths);
+            } else if attr.path().is_ident("forbids") {
+                let paths =
+                    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                expectations.forbidden.extend(paths);
+            } else if attr.path().is_ident("expect_fn") {
+                let paths =
+                    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                expectations.expected_fns.extend(paths);
+            } else if attr.path().is_ident("expect_include") {
+                let paths =
+                    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                expectations.includes.extend(paths);
+            } else if attr.path().is_ident("expect") {
+                reject_components_in_lint_attr(attr)?;
+            }
+        }
+
+        expectations.dedup_expected();
+        Ok(expectations)
+    }
+
+    /// Records the components of one `#[expects(...)]` attribute along with
+    /// the options that apply to them.
+    fn add_expects(&mut self, args: ExpectsArgs) {
+        if args.require_prior {
+            self.prior.extend(args.paths.iter().cloned());
+        }
+        if let Some(stability) = args.stability {
+            self.stability.extend(
+                args.paths
+                    .iter()
+                    .map(|path| (path.clone(), stability.clone())),
+            );
+        }
+        if let Some(mode) = args.mode {
+            self.modes
+                .extend(args.paths.iter().map(|path| (path.clone(), mode.clone())));
+        }
+        if let Some(message) = args.message {
+            self.messages.extend(
+                args.paths
+                    .iter()
+                    .map(|path| (path.clone(), message.clone())),
+            );
+        }
+        if !args.tags.is_empty() {
+            self.tags.extend(
+                args.paths
+                    .iter()
+                    .map(|path| (path.clone(), args.tags.clone())),
+            );
+        }
+        self.expected.extend(args.paths);
+    }
+
+    /// Records the `#[expects(...)]` attributes on each variant of an enum.
+    fn add_variants(&mut self, data: &DataEnum) -> syn::Result<()> {
+        for variant in &data.variants {
+            for attr in &variant.attrs {
+                if attr.path().is_ident("expects") {
+                    let paths =
+                        attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                    self.variants
+                        .extend(paths.into_iter().map(|path| (variant.ident.clone(), path)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drops repeated expected paths, keeping the first, so a component named
+    /// by several attributes is checked once.
+    ///
+    /// Paths are compared as written. Two different spellings of one type,
+    /// such as an alias and its definition, are both kept and checked twice,
+    /// which is redundant but harmless.
+    fn dedup_expected(&mut self) {
+        let mut seen = HashSet::new();
+        self.expected
+            .retain(|path| seen.insert(quote!(#path).to_string()));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.expected.is_empty()
+            && self.includes.is_empty()
+            && self.expected_fns.is_empty()
+            && self.unless_present.is_empty()
+            && self.conditional.is_empty()
+            && self.both_ends.is_empty()
+            && self.on_related.is_empty()
+            && self.incoming.is_empty()
+            && self.all_children.is_empty()
+            && self.child_counts.is_empty()
+            && self.on_parent.is_empty()
+            && self.forbidden.is_empty()
+            && self.resources.is_empty()
+            && self.any_of.is_empty()
+            && self.one_of.is_empty()
+            && self.variants.is_empty()
+    }
+
+    /// Generates the `ExpectComponents` methods that override the trait defaults.
+    fn trait_methods(&self) -> TokenStream2 {
+        [
+            self.expected_methods(),
+            self.unless_present_methods(),
+            self.conditional_methods(),
+            self.forbidden_methods(),
+            self.resource_methods(),
+            self.any_of_methods(),
+            self.one_of_methods(),
+            self.prior_methods(),
+            self.non_default_methods(),
+            self.predicate_methods(),
+            self.variant_methods(),
+            self.stability_methods(),
+            self.tag_methods(),
+            self.mode_methods(),
+            self.message_methods(),
+            self.relationship_methods(),
+            self.children_methods(),
+            self.parent_methods(),
+            self.include_required_method(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn expected_methods(&self) -> TokenStream2 {
+        let included = |method: TokenStream2| {
+            self.includes.iter().map(move |include| {
+                quote! { <#include as ::bevy_expected_components::ExpectComponents>::#method() }
+            })
+        };
+
+        let computed: Vec<TokenStream2> =
+            self.expected_fns.iter().map(|f| quote! { #f() }).collect();
+        let extra_ids: Vec<TokenStream2> = included(quote! { expected_components })
+            .chain(computed.iter().cloned())
+            .collect();
+        let extra_names: Vec<TokenStream2> =
+            included(quote! { expected_component_names }).collect();
+
+        let ids = merged_slice(
+            &quote! { ::std::any::TypeId },
+            self.expected.iter().map(type_id),
+            &extra_ids,
+        );
+        let names = merged_slice(
+            &quote! { &'static str },
+            self.expected.iter().map(type_name),
+            &extra_names,
+        );
+
+        if computed.is_empty() {
+            return quote! {
+                fn expected_components() -> &'static [::std::any::TypeId] {
+                    #ids
+                }
+
+                fn expected_component_names() -> &'static [&'static str] {
+                    #names
+                }
+            };
+        }
+
+        // `#[expect_fn]` only supplies `TypeId`s, so their names are resolved from
+        // worlds the components are registered in
+        let computed = merged_slice(
+            &quote! { ::std::any::TypeId },
+            std::iter::empty(),
+            &computed,
+        );
+        quote! {
             fn expected_components() -> &'static [::std::any::TypeId] {
-                static IDS: ::std::sync::OnceLock<::std::vec::Vec<::std::any::TypeId>> =
-                    ::std::sync::OnceLock::new();
-                IDS.get_or_init(|| ::std::vec![#(#type_ids),*]).as_slice()
+                #ids
             }
 
             fn expected_component_names() -> &'static [&'static str] {
-                static NAMES: ::std::sync::OnceLock<::std::vec::Vec<&'static str>> =
-                    ::std::sync::OnceLock::new();
-                NAMES.get_or_init(|| ::std::vec![#(#type_names),*]).as_slice()
+                let named: &'static [&'static str] = { #names };
+                ::bevy_expected_components::__with_computed_names(
+                    ::std::any::TypeId::of::<Self>(),
+                    named,
+                    <Self as ::bevy_expected_components::ExpectComponents>::computed_components(),
+                )
+            }
+
+            fn computed_components() -> &'static [::std::any::TypeId] {
+                #computed
             }
         }
+    }
+
+    fn unless_present_methods(&self) -> TokenStream2 {
+        if self.unless_present.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let ids = static_slice(
+            &quote! { (::std::any::TypeId, ::std::any::TypeId) },
+            self.unless_present.iter().map(|(absent, required)| {
+                let (absent, required) = (type_id(absent), type_id(required));
+                quote! { (#absent, #required) }
+            }),
+        );
+        let names = static_slice(
+            &quote! { (&'static str, &'static str) },
+            self.unless_present.iter().map(|(absent, required)| {
+                let (absent, required) = (type_name(absent), type_name(required));
+                quote! { (#absent, #required) }
+            }),
+        );
 
-        ::bevy_expected_components::inventory::submit! {
-            ::bevy_expected_components::ExpectRegistration::new(
-                ::bevy_expected_components::register_hooks_for::<#name>
-            )
+        quote! {
+            fn expected_unless_present() -> &'static [(::std::any::TypeId, ::std::any::TypeId)] {
+                #ids
+            }
+
+            fn expected_unless_present_names() -> &'static [(&'static str, &'static str)] {
+                #names
+            }
         }
+    }
+
+    fn conditional_methods(&self) -> TokenStream2 {
+        if self.conditional.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let ids = static_slice(
+            &quote! { (::std::any::TypeId, ::std::any::TypeId) },
+            self.conditional.iter().map(|(guard, required)| {
+                let (guard, required) = (type_id(guard), type_id(required));
+                quote! { (#guard, #required) }
+            }),
+        );
+        let names = static_slice(
+            &quote! { (&'static str, &'static str) },
+            self.conditional.iter().map(|(guard, required)| {
+                let (guard, required) = (type_name(guard), type_name(required));
+                quote! { (#guard, #required) }
+            }),
+        );
+
+        quote! {
+            fn expected_if() -> &'static [(::std::any::TypeId, ::std::any::TypeId)] {
+                #ids
+            }
+
+            fn expected_if_names() -> &'static [(&'static str, &'static str)] {
+                #names
+            }
+        }
+    }
+
+    fn forbidden_methods(&self) -> TokenStream2 {
+        if self.forbidden.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let forbidden = &self.forbidden;
+        let ids = static_slice(
+            &quote! { ::std::any::TypeId },
+            forbidden.iter().map(type_id),
+        );
+        let names = static_slice(&quote! { &'static str }, forbidden.iter().map(type_name));
+
+        quote! {
+            fn forbidden_components() -> &'static [::std::any::TypeId] {
+                #ids
+            }
+
+            fn forbidden_component_names() -> &'static [&'static str] {
+                #names
+            }
+        }
+    }
+
+    fn resource_methods(&self) -> TokenStream2 {
+        if self.resources.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let resources = &self.resources;
+        let ids = static_slice(
+            &quote! { ::std::any::TypeId },
+            resources.iter().map(type_id),
+        );
+        let names = static_slice(&quote! { &'static str }, resources.iter().map(type_name));
+
+        quote! {
+            fn expected_resources() -> &'static [::std::any::TypeId] {
+                #ids
+            }
+
+            fn expected_resource_names() -> &'static [&'static str] {
+                #names
+            }
+        }
+    }
+
+    fn any_of_methods(&self) -> TokenStream2 {
+        if self.any_of.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let (ids, names) = group_slices(&self.any_of);
+
+        quote! {
+            fn expected_any() -> &'static [&'static [::std::any::TypeId]] {
+                #ids
+            }
+
+            fn expected_any_names() -> &'static [&'static [&'static str]] {
+                #names
+            }
+        }
+    }
+
+    fn one_of_methods(&self) -> TokenStream2 {
+        if self.one_of.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let (ids, names) = group_slices(&self.one_of);
+
+        quote! {
+            fn expected_one_of() -> &'static [&'static [::std::any::TypeId]] {
+                #ids
+            }
+
+            fn expected_one_of_names() -> &'static [&'static [&'static str]] {
+                #names
+            }
+        }
+    }
+
+    fn prior_methods(&self) -> TokenStream2 {
+        if self.prior.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let prior = &self.prior;
+        let ids = static_slice(&quote! { ::std::any::TypeId }, prior.iter().map(type_id));
+        let names = static_slice(&quote! { &'static str }, prior.iter().map(type_name));
+
+        quote! {
+            fn expected_prior() -> &'static [::std::any::TypeId] {
+                #ids
+            }
+
+            fn expected_prior_names() -> &'static [&'static str] {
+                #names
+            }
+
+            fn register_add_order_tracking(world: &mut ::bevy_expected_components::__World) {
+                #(::bevy_expected_components::track_add_order::<#prior>(world);)*
+            }
+        }
+    }
+
+    fn non_default_methods(&self) -> TokenStream2 {
+        if self.non_default.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let non_default = static_slice(
+            &quote! { ::bevy_expected_components::NonDefaultExpectation },
+            self.non_default.iter().map(|component| {
+                let name = type_name(component);
+                quote! {
+                    ::bevy_expected_components::NonDefaultExpectation {
+                        component: #name,
+                        is_default: ::bevy_expected_components::is_default_value::<#component>,
+                        was_mutated: ::bevy_expected_components::was_mutated::<#component>,
+                    }
+                }
+            }),
+        );
+
+        quote! {
+            fn expected_non_default() -> &'static [::bevy_expected_components::NonDefaultExpectation] {
+                #non_default
+            }
+        }
+    }
+
+    fn predicate_methods(&self) -> TokenStream2 {
+        if self.predicates.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let predicates = static_slice(
+            &quote! { ::bevy_expected_components::PredicateExpectation },
+            self.predicates.iter().map(
+                |PredicateArgs {
+                     component,
+                     predicate,
+                 }| {
+                    let name = type_name(component);
+                    quote! {
+                        ::bevy_expected_components::PredicateExpectation {
+                            component: #name,
+                            fails: |world, entity| {
+                                world
+                                    .get::<#component>(entity)
+                                    .is_some_and(|value| !(#predicate)(value))
+                            },
+                            was_mutated: ::bevy_expected_components::was_mutated::<#component>,
+                        }
+                    }
+                },
+            ),
+        );
+
+        quote! {
+            fn expected_predicates() -> &'static [::bevy_expected_components::PredicateExpectation] {
+                #predicates
+            }
+        }
+    }
+
+    fn variant_methods(&self) -> TokenStream2 {
+        if self.variants.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let variants = static_slice(
+            &quote! { ::bevy_expected_components::VariantExpectation },
+            self.variants.iter().map(|(variant, component)| {
+                let variant_name = variant.to_string();
+                let component_id = type_id(component);
+                let component_name = type_name(component);
+                quote! {
+                    ::bevy_expected_components::VariantExpectation {
+                        variant: #variant_name,
+                        component: #component_id,
+                        component_name: #component_name,
+                        is_active: |world, entity| {
+                            ::core::matches!(world.get::<Self>(entity), ::core::option::Option::Some(Self::#variant { .. }))
+                        },
+                    }
+                }
+            }),
+        );
+
+        quote! {
+            fn expected_by_variant() -> &'static [::bevy_expected_components::VariantExpectation] {
+                #variants
+            }
+        }
+    }
+
+    fn stability_methods(&self) -> TokenStream2 {
+        if self.stability.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let stability = static_slice(
+            &quote! { (&'static str, ::bevy_expected_components::Stability) },
+            self.stability.iter().map(|(component, stability)| {
+                let name = type_name(component);
+                quote! { (#name, ::bevy_expected_components::Stability::#stability) }
+            }),
+        );
+
+        quote! {
+            fn expected_stability() -> &'static [(&'static str, ::bevy_expected_components::Stability)] {
+                #stability
+            }
+        }
+    }
+
+    fn tag_methods(&self) -> TokenStream2 {
+        if self.tags.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let tags = static_slice(
+            &quote! { (&'static str, &'static [&'static str]) },
+            self.tags.iter().map(|(component, tags)| {
+                let name = type_name(component);
+                quote! { (#name, &[#(#tags),*]) }
+            }),
+        );
+
+        quote! {
+            fn expected_tags() -> &'static [(&'static str, &'static [&'static str])] {
+                #tags
+            }
+        }
+    }
+
+    fn mode_methods(&self) -> TokenStream2 {
+        if self.modes.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let modes = static_slice(
+            &quote! { (&'static str, ::bevy_expected_components::FailureMode) },
+            self.modes.iter().map(|(component, mode)| {
+                let name = type_name(component);
+                quote! { (#name, ::bevy_expected_components::FailureMode::#mode) }
+            }),
+        );
+
+        quote! {
+            fn expected_modes() -> &'static [(&'static str, ::bevy_expected_components::FailureMode)] {
+                #modes
+            }
+        }
+    }
+
+    fn message_methods(&self) -> TokenStream2 {
+        if self.messages.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let messages = static_slice(
+            &quote! { (&'static str, &'static str) },
+            self.messages.iter().map(|(component, message)| {
+                let name = type_name(component);
+                quote! { (#name, #message) }
+            }),
+        );
+
+        quote! {
+            fn expected_messages() -> &'static [(&'static str, &'static str)] {
+                #messages
+            }
+        }
+    }
+
+    fn relationship_methods(&self) -> TokenStream2 {
+        let mut methods = TokenStream2::new();
+
+        if !self.both_ends.is_empty() {
+            let both_ends = static_slice(
+                &quote! { ::bevy_expected_components::RelatedExpectation },
+                self.both_ends
+                    .iter()
+                    .map(|(relationship, component)| related_expectation(relationship, component)),
+            );
+            methods.extend(quote! {
+                fn expected_on_both_ends() -> &'static [::bevy_expected_components::RelatedExpectation] {
+                    #both_ends
+                }
+            });
+        }
+
+        if !self.on_related.is_empty() {
+            let on_related = static_slice(
+                &quote! { ::bevy_expected_components::RelatedExpectation },
+                self.on_related
+                    .iter()
+                    .map(|(relationship, component)| related_expectation(relationship, component)),
+            );
+            methods.extend(quote! {
+                fn expected_on_related() -> &'static [::bevy_expected_components::RelatedExpectation] {
+                    #on_related
+                }
+            });
+        }
+
+        if !self.incoming.is_empty() {
+            let incoming = static_slice(
+                &quote! { ::bevy_expected_components::IncomingExpectation },
+                self.incoming.iter().map(IncomingArgs::to_expectation),
+            );
+            methods.extend(quote! {
+                fn expected_incoming() -> &'static [::bevy_expected_components::IncomingExpectation] {
+                    #incoming
+                }
+            });
+        }
+
+        methods
+    }
+
+    fn children_methods(&self) -> TokenStream2 {
+        let mut methods = self.all_children_methods();
+
+        if !self.child_counts.is_empty() {
+            let counts = static_slice(
+                &quote! { ::bevy_expected_components::ChildCountExpectation },
+                self.child_counts.iter().map(ChildCountArgs::to_expectation),
+            );
+            methods.extend(quote! {
+                fn expected_child_counts() -> &'static [::bevy_expected_components::ChildCountExpectation] {
+                    #counts
+                }
+            });
+        }
+
+        methods
+    }
+
+    fn all_children_methods(&self) -> TokenStream2 {
+        if self.all_children.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let ids = static_slice(
+            &quote! { ::std::any::TypeId },
+            self.all_children.iter().map(type_id),
+        );
+        let names = static_slice(
+            &quote! { &'static str },
+            self.all_children.iter().map(type_name),
+        );
+
+        quote! {
+            fn expected_on_all_children() -> &'static [::std::any::TypeId] {
+                #ids
+            }
+
+            fn expected_on_all_children_names() -> &'static [&'static str] {
+                #names
+            }
+        }
+    }
+
+    fn parent_methods(&self) -> TokenStream2 {
+        if self.on_parent.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let ids = static_slice(
+            &quote! { ::std::any::TypeId },
+            self.on_parent.iter().map(type_id),
+        );
+        let names = static_slice(
+            &quote! { &'static str },
+            self.on_parent.iter().map(type_name),
+        );
+
+        quote! {
+            fn expected_on_parent() -> &'static [::std::any::TypeId] {
+                #ids
+            }
+
+            fn expected_on_parent_names() -> &'static [&'static str] {
+                #names
+            }
+        }
+    }
+
+    fn include_required_method(&self) -> TokenStream2 {
+        if !self.include_required {
+            return TokenStream2::new();
+        }
+
+        quote! {
+            fn include_required_components() -> bool {
+                true
+            }
+        }
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let mut expectations = Expectations::from_attrs(&input.attrs)?;
+    if let Data::Enum(data) = &input.data {
+        expectations.add_variants(data)?;
+    }
+
+    if expectations.is_empty() {
+        return Err(syn::Error::new_spanned(
+            name,
+            "ExpectComponents derive requires at least one #[expects(Component)] attribute",
+        ));
+    }
+
+    reject_self_expectation(name, &expectations)?;
+
+    let methods = expectations.trait_methods();
+
+    let vis = &input.vis;
+    let debug_assert_fn = format_ident!("debug_assert_{}_expectations", to_snake_case(name));
+    let debug_assert_doc = format!(
+        "Panics if the entity fails `{name}`'s expectations. Does nothing in release builds."
+    );
+    let registrar = registrar_ident(name);
+    let registrar_doc = format!(
+        "Registers `{name}`'s expectations without `inventory`. Call it through `register_all_expectations!`."
+    );
+
+    let ValidationItems {
+        debug_assert_body,
+        registrar_body,
+        submission,
+    } = ValidationItems::for_type(name);
+
+    Ok(quote! {
+        impl ::bevy_expected_components::ExpectComponents for #name {
+            #methods
+        }
+
+        #[doc = #debug_assert_doc]
+        #[allow(dead_code)]
+        #vis fn #debug_assert_fn(entity: &::bevy_expected_components::__EntityRef) {
+            #debug_assert_body
+        }
+
+        #[doc = #registrar_doc]
+        #[doc(hidden)]
+        #[allow(dead_code, non_snake_case)]
+        #vis fn #registrar() {
+            #registrar_body
+        }
+
+        #submission
+    })
+}
+
+/// The parts of the derive output that validate or register the type.
+struct ValidationItems {
+    debug_assert_body: TokenStream2,
+    registrar_body: TokenStream2,
+    submission: TokenStream2,
+}
+
+impl ValidationItems {
+    #[cfg(feature = "validate")]
+    fn for_type(name: &syn::Ident) -> Self {
+        ValidationItems {
+            debug_assert_body: quote! {
+                if ::core::cfg!(debug_assertions) {
+                    ::bevy_expected_components::assert_entity_expectations::<#name>(entity);
+                }
+            },
+            registrar_body: quote! {
+                ::bevy_expected_components::__submit_registration::<#name>();
+            },
+            submission: quote! {
+                ::bevy_expected_components::inventory::submit! {
+                    ::bevy_expected_components::ExpectRegistration::of::<#name>()
+                }
+            },
+        }
+    }
+
+    /// Without `validate`, nothing refers to the generated metadata, so it is
+    /// stripped from the final binary.
+    #[cfg(not(feature = "validate"))]
+    fn for_type(_name: &syn::Ident) -> Self {
+        ValidationItems {
+            debug_assert_body: quote! { let _ = entity; },
+            registrar_body: TokenStream2::new(),
+            submission: TokenStream2::new(),
+        }
+    }
+}
+
+/// Names the registrar function generated for the expecting type `ident`.
+fn registrar_ident(ident: &syn::Ident) -> syn::Ident {
+    format_ident!("__register_{}", ident, span = ident.span())
+}
+
+/// Rewrites the path of an expecting type into the path of its registrar,
+/// which the derive generates next to the type.
+fn registrar_path(mut path: Path) -> syn::Result<Path> {
+    let Some(last) = path.segments.last_mut() else {
+        return Err(syn::Error::new_spanned(&path, "expected a type path"));
     };
+    if !last.arguments.is_none() {
+        return Err(syn::Error::new_spanned(
+            &last.arguments,
+            "list expecting types without generic arguments",
+        ));
+    }
+
+    last.ident = registrar_ident(&last.ident);
+    Ok(path)
+}
+
+/// Generates a function body returning a lazily initialized `&'static [#ty]`.
+fn static_slice(ty: &TokenStream2, values: impl Iterator<Item = TokenStream2>) -> TokenStream2 {
+    quote! {
+        static VALUES: ::std::sync::OnceLock<::std::vec::Vec<#ty>> =
+            ::std::sync::OnceLock::new();
+        VALUES.get_or_init(|| ::std::vec![#(#values),*]).as_slice()
+    }
+}
+
+/// Like [`static_slice`], but appends each of `extras`, expressions evaluating to
+/// slices or vectors of `ty`, when the slice is first built.
+fn merged_slice(
+    ty: &TokenStream2,
+    values: impl Iterator<Item = TokenStream2>,
+    extras: &[TokenStream2],
+) -> TokenStream2 {
+    if extras.is_empty() {
+        return static_slice(ty, values);
+    }
+
+    quote! {
+        static VALUES: ::std::sync::OnceLock<::std::vec::Vec<#ty>> =
+            ::std::sync::OnceLock::new();
+        VALUES
+            .get_or_init(|| {
+                let mut values = ::std::vec![#(#values),*];
+                #(values.extend_from_slice(&#extras);)*
+                values
+            })
+            .as_slice()
+    }
+}
+
+/// Generates static slices of the `TypeId`s and names of each group of components.
+fn group_slices(groups: &[Vec<Path>]) -> (TokenStream2, TokenStream2) {
+    // Each group is leaked once, on first use, to give it a static lifetime
+    let ids = static_slice(
+        &quote! { &'static [::std::any::TypeId] },
+        groups.iter().map(|group| {
+            let ids = group.iter().map(type_id);
+            quote! { ::std::vec![#(#ids),*].leak() }
+        }),
+    );
+    let names = static_slice(
+        &quote! { &'static [&'static str] },
+        groups.iter().map(|group| {
+            let names = group.iter().map(type_name);
+            quote! { ::std::vec![#(#names),*].leak() }
+        }),
+    );
+    (ids, names)
+}
+
+/// Generates a `RelatedExpectation` for a relationship and an expected component.
+fn related_expectation(relationship: &Path, component: &Path) -> TokenStream2 {
+    let relationship_name = type_name(relationship);
+    let expected = type_id(component);
+    let expected_name = type_name(component);
+
+    quote! {
+        ::bevy_expected_components::RelatedExpectation {
+            relationship: #relationship_name,
+            target: ::bevy_expected_components::relationship_target::<#relationship>,
+            expected: #expected,
+            expected_name: #expected_name,
+        }
+    }
+}
+
+/// Generates a `TypeId` expression for a component path.
+fn type_id(path: &Path) -> TokenStream2 {
+    quote! { ::std::any::TypeId::of::<#path>() }
+}
+
+/// Generates a type name expression for a component path, used in error messages.
+///
+/// The path is resolved at the derive site, so aliases and re-exports are named
+/// by the type's defining path rather than as written.
+fn type_name(path: &Path) -> TokenStream2 {
+    quote! { ::std::any::type_name::<#path>() }
+}
+
+/// Parses an attribute of the form `#[attr(A, B)]` into exactly two paths.
+fn parse_path_pair(attr: &Attribute) -> syn::Result<(Path, Path)> {
+    let attr_path = attr.path();
+    let paths = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+    let mut iter = paths.into_iter();
+    match (iter.next(), iter.next(), iter.next()) {
+        (Some(first), Some(second), None) => Ok((first, second)),
+        _ => Err(syn::Error::new_spanned(
+            attr,
+            format!(
+                "expected exactly two types, e.g. #[{}(A, B)]",
+                quote!(#attr_path).to_string().replace(' ', "")
+            ),
+        )),
+    }
+}
+
+/// Arguments of an `#[expects(...)]` attribute: component paths plus optional flags.
+struct ExpectsArgs {
+    paths: Vec<Path>,
+    require_prior: bool,
+    stability: Option<TokenStream2>,
+    tags: Vec<LitStr>,
+    mode: Option<TokenStream2>,
+    message: Option<LitStr>,
+}
+
+impl Parse for ExpectsArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = ExpectsArgs {
+            paths: Vec::new(),
+            require_prior: false,
+            stability: None,
+            tags: Vec::new(),
+            mode: None,
+            message: None,
+        };
+
+        while !input.is_empty() {
+            let path: Path = input.parse()?;
+            if path.is_ident("stability") && input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                args.stability = Some(parse_stability(&input.parse()?)?);
+            } else if path.is_ident("mode") && input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                args.mode = Some(parse_mode(&input.parse()?)?);
+            } else if path.is_ident("level") && input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                args.mode = Some(parse_level(&input.parse()?)?);
+            } else if path.is_ident("message") && input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                args.message = Some(input.parse()?);
+            } else if path.is_ident("tags") && input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                let content;
+                syn::bracketed!(content in input);
+                args.tags
+                    .extend(Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?);
+            } else if path.is_ident("group") && input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                // A group is a tag that `ExpectationGroups` can switch off
+                args.tags.push(input.parse()?);
+            } else if path.is_ident("require_prior") {
+                args.require_prior = true;
+            } else {
+                args.paths.push(path);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(args)
+    }
+}
+
+/// Fails if a `#[expect(...)]` attribute names components instead of lints.
+///
+/// `expect` is Rust's built-in lint attribute, so it cannot double as a
+/// spelling of `expects`. Lint names are snake case, so an argument whose last
+/// segment starts with an uppercase letter is a component written with the
+/// wrong attribute.
+fn reject_components_in_lint_attr(attr: &Attribute) -> syn::Result<()> {
+    let Ok(paths) = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated) else {
+        return Ok(());
+    };
+
+    for path in &paths {
+        let is_type = path.segments.last().is_some_and(|segment| {
+            segment
+                .ident
+                .to_string()
+                .starts_with(|c: char| c.is_ascii_uppercase())
+        });
+        if is_type {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "`#[expect]` is Rust's lint attribute; use `#[expects({})]` to expect a component",
+                    quote!(#path).to_string().replace(' ', "")
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
 
-    expanded.into()
+/// Rejects a type expecting itself, which no entity can satisfy when the
+/// component is added, or listing itself among alternatives, where its own
+/// presence would decide the expectation.
+///
+/// Only `Self` and the type's bare name count as the type itself, so a
+/// different type with the same name, such as `physics::Body` expected by
+/// `render::Body`, is accepted.
+fn reject_self_expectation(name: &syn::Ident, expectations: &Expectations) -> syn::Result<()> {
+    let mut never_satisfied = expectations.expected.iter().chain(
+        expectations
+            .conditional
+            .iter()
+            .map(|(_, required)| required),
+    );
+    if let Some(path) = never_satisfied.find(|path| is_self(name, path)) {
+        return Err(syn::Error::new_spanned(
+            path,
+            format!("{name} cannot expect itself; the expectation could never be satisfied"),
+        ));
+    }
+
+    let mut alternatives = expectations
+        .any_of
+        .iter()
+        .chain(&expectations.one_of)
+        .flatten();
+    if let Some(path) = alternatives.find(|path| is_self(name, path)) {
+        return Err(syn::Error::new_spanned(
+            path,
+            format!("{name} cannot list itself as an alternative; it is always present on its own entities"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns true if `path` names the type `name` itself.
+fn is_self(name: &syn::Ident, path: &Path) -> bool {
+    path.leading_colon.is_none()
+        && path.segments.len() == 1
+        && (path.segments[0].ident == *name || path.segments[0].ident == "Self")
+}
+
+/// Maps a `level = ...` severity to the `FailureMode` variant that reports at
+/// that level.
+fn parse_level(value: &syn::Ident) -> syn::Result<TokenStream2> {
+    match value.to_string().as_str() {
+        "panic" => Ok(quote! { Panic }),
+        "error" => Ok(quote! { Log }),
+        "warn" => Ok(quote! { Warn }),
+        _ => Err(syn::Error::new_spanned(
+            value,
+            "expected `level = panic`, `error` or `warn`",
+        )),
+    }
+}
+
+/// Maps a `mode = "..."` value to the matching `FailureMode` variant.
+fn parse_mode(value: &LitStr) -> syn::Result<TokenStream2> {
+    match value.value().as_str() {
+        "panic" => Ok(quote! { Panic }),
+        "warn" => Ok(quote! { Warn }),
+        "log" => Ok(quote! { Log }),
+        "silent" => Ok(quote! { Silent }),
+        "warn_once" => Ok(quote! { WarnOnce }),
+        _ => Err(syn::Error::new_spanned(
+            value,
+            "expected `mode = \"panic\"`, `\"warn\"`, `\"warn_once\"`, `\"log\"` or `\"silent\"`",
+        )),
+    }
+}
+
+/// Maps a `stability = "..."` value to the matching `Stability` variant.
+fn parse_stability(value: &LitStr) -> syn::Result<TokenStream2> {
+    match value.value().as_str() {
+        "stable" => Ok(quote! { Stable }),
+        "experimental" => Ok(quote! { Experimental }),
+        _ => Err(syn::Error::new_spanned(
+            value,
+            "expected `stability = \"stable\"` or `stability = \"experimental\"`",
+        )),
+    }
+}
+
+/// Arguments of `#[expect_incoming_relations(Relationship, min = N)]`.
+struct IncomingArgs {
+    relationship: Path,
+    min: LitInt,
+}
+
+impl Parse for IncomingArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let relationship = input.parse()?;
+        input.parse::<Token![,]>()?;
+
+        let key: syn::Ident = input.parse()?;
+        if key != "min" {
+            return Err(syn::Error::new_spanned(key, "expected `min = <count>`"));
+        }
+        input.parse::<Token![=]>()?;
+        let min = input.parse()?;
+
+        Ok(IncomingArgs { relationship, min })
+    }
+}
+
+impl IncomingArgs {
+    /// Generates the `IncomingExpectation` these arguments describe.
+    fn to_expectation(&self) -> TokenStream2 {
+        let relationship = &self.relationship;
+        let relationship_name = type_name(relationship);
+        let min = &self.min;
+
+        quote! {
+            ::bevy_expected_components::IncomingExpectation {
+                relationship: #relationship_name,
+                count: ::bevy_expected_components::incoming_relationship_count::<#relationship>,
+                min: #min,
+            }
+        }
+    }
+}
+
+/// Arguments of `#[expect_where(Component, predicate)]`.
+struct PredicateArgs {
+    component: Path,
+    predicate: syn::Expr,
+}
+
+impl Parse for PredicateArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let component = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let predicate = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self {
+            component,
+            predicate,
+        })
+    }
+}
+
+/// Arguments of `#[expect_children(Component, min = N, max = M)]`, where
+/// either bound may be left out.
+struct ChildCountArgs {
+    component: Path,
+    min: Option<LitInt>,
+    max: Option<LitInt>,
+}
+
+impl Parse for ChildCountArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = ChildCountArgs {
+            component: input.parse()?,
+            min: None,
+            max: None,
+        };
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let key: syn::Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            if key == "min" {
+                args.min = Some(input.parse()?);
+            } else if key == "max" {
+                args.max = Some(input.parse()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    "expected `min = <count>` or `max = <count>`",
+                ));
+            }
+        }
+
+        if args.min.is_none() && args.max.is_none() {
+            return Err(syn::Error::new_spanned(
+                &args.component,
+                "expected at least one of `min = <count>` and `max = <count>`",
+            ));
+        }
+
+        Ok(args)
+    }
+}
+
+impl ChildCountArgs {
+    /// Generates the `ChildCountExpectation` these arguments describe.
+    fn to_expectation(&self) -> TokenStream2 {
+        let component = type_id(&self.component);
+        let component_name = type_name(&self.component);
+        let min = self
+            .min
+            .as_ref()
+            .map_or_else(|| quote! { 0 }, |min| quote! { #min });
+        let max = self.max.as_ref().map_or_else(
+            || quote! { ::core::option::Option::None },
+            |max| quote! { ::core::option::Option::Some(#max) },
+        );
+
+        quote! {
+            ::bevy_expected_components::ChildCountExpectation {
+                component: #component,
+                component_name: #component_name,
+                min: #min,
+                max: #max,
+            }
+        }
+    }
+}
+
+/// Converts a `CamelCase` identifier to `snake_case`.
+fn to_snake_case(ident: &syn::Ident) -> String {
+    let mut snake = String::new();
+    for (i, ch) in ident.to_string().chars().enumerate() {
+        if ch.is_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(ch);
+        }
+    }
+    snake
 }
@@ -0,0 +1,55 @@
+//! Tracking the order in which expected components are added.
+//!
+//! Bevy runs every `on_add` hook for an insert before any `Add` observer. The
+//! observers installed here therefore record a component only once its own
+//! insert has finished, so a hook that finds the component on the entity but
+//! not in the tracker knows it arrived in the same operation.
+
+use std::any::TypeId;
+use std::collections::HashSet;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::lifecycle::{Add, Remove};
+use bevy_ecs::observer::On;
+use bevy_ecs::resource::Resource;
+use bevy_ecs::system::ResMut;
+use bevy_ecs::world::World;
+
+/// Records which tracked components each entity gained in an earlier operation.
+///
+/// Populated by the observers installed through [`track_add_order`].
+#[derive(Resource, Default, Debug)]
+pub struct AddOrderTracker {
+    tracked: HashSet<TypeId>,
+    added: HashSet<(Entity, TypeId)>,
+}
+
+/// Installs observers that record when `C` is added to or removed from an entity.
+///
+/// Called for each `require_prior` expectation. Calling it more than once for the
+/// same type has no further effect.
+pub fn track_add_order<C: Component>(world: &mut World) {
+    let mut tracker = world.get_resource_or_init::<AddOrderTracker>();
+    if !tracker.tracked.insert(TypeId::of::<C>()) {
+        return;
+    }
+
+    world.add_observer(|add: On<Add, C>, mut tracker: ResMut<AddOrderTracker>| {
+        tracker.added.insert((add.entity, TypeId::of::<C>()));
+    });
+    world.add_observer(
+        |remove: On<Remove, C>, mut tracker: ResMut<AddOrderTracker>| {
+            tracker.added.remove(&(remove.entity, TypeId::of::<C>()));
+        },
+    );
+}
+
+/// Returns true if the component was added to the entity in an earlier operation.
+///
+/// Components added before tracking was installed are reported as not prior.
+pub(crate) fn was_added_before(world: &World, entity: Entity, type_id: TypeId) -> bool {
+    world
+        .get_resource::<AddOrderTracker>()
+        .is_some_and(|tracker| tracker.added.contains(&(entity, type_id)))
+}
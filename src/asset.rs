@@ -0,0 +1,114 @@
+//! Expectations checked against the component set declared by a loaded asset.
+
+use std::any::TypeId;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::{DeferredWorld, World};
+
+use crate::reported::ReportedFailures;
+use crate::{report, validation_enabled, ExpectationViolation, ViolationKind};
+
+/// Component set declared by the asset a component refers to.
+///
+/// Returned by the extractor passed to [`register_asset_expectation`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeclaredComponents {
+    /// The asset is still loading. Validation is skipped until it finishes.
+    Loading,
+    /// The asset has loaded and declares these component types.
+    Loaded(Vec<TypeId>),
+}
+
+type AssetCheck = Box<dyn Fn(&World) -> Vec<ExpectationViolation> + Send + Sync>;
+
+/// Registered asset expectations, checked once per frame in the `Last` schedule.
+#[derive(Resource, Default)]
+pub(crate) struct AssetExpectations {
+    checks: Vec<AssetCheck>,
+    reported: ReportedFailures,
+}
+
+/// Registers an expectation that the asset referenced by `C` declares `E`.
+///
+/// The extractor resolves the asset behind a `C` component, typically by looking
+/// up its handle in the asset storage, and reports the component types the asset
+/// declares. [`ExpectedComponentsPlugin`](crate::ExpectedComponentsPlugin) checks
+/// every entity with `C` at the end of each frame, skipping assets that are still
+/// loading. Each failure is reported once, until it is fixed.
+///
+/// ```rust,ignore
+/// register_asset_expectation::<PrefabRef, Collider>(app.world_mut(), |world, prefab| {
+///     match world.resource::<Assets<PrefabAsset>>().get(&prefab.0) {
+///         Some(asset) => DeclaredComponents::Loaded(asset.component_types()),
+///         None => DeclaredComponents::Loading,
+///     }
+/// });
+/// ```
+pub fn register_asset_expectation<C: Component, E: Component>(
+    world: &mut World,
+    extractor: fn(&World, &C) -> DeclaredComponents,
+) {
+    let check = move |world: &World| {
+        let Some(mut query) = world.try_query::<(Entity, &C)>() else {
+            return Vec::new();
+        };
+
+        query
+            .iter(world)
+            .filter(|(_, component)| match extractor(world, component) {
+                DeclaredComponents::Loading => false,
+                DeclaredComponents::Loaded(declared) => !declared.contains(&TypeId::of::<E>()),
+            })
+            .map(|(entity, _)| {
+                let expecting = std::any::type_name::<C>();
+                let missing = std::any::type_name::<E>();
+                ExpectationViolation {
+                    entity,
+                    expecting,
+                    missing,
+                    message: format!(
+                        "{expecting} expects its asset to declare {missing} but it was not declared for entity {entity:?}"
+                    ),
+                    kind: ViolationKind::Other,
+                    caller: None,
+                }
+            })
+            .collect()
+    };
+
+    world
+        .get_resource_or_init::<AssetExpectations>()
+        .checks
+        .push(Box::new(check));
+}
+
+/// Checks every registered asset expectation, reporting each failure once.
+///
+/// A failure is reported again only after it has been fixed and then recurs.
+pub(crate) fn validate_asset_expectations(world: &mut World) {
+    if !validation_enabled(world) {
+        return;
+    }
+
+    let Some(expectations) = world.get_resource::<AssetExpectations>() else {
+        return;
+    };
+
+    let violations: Vec<ExpectationViolation> = expectations
+        .checks
+        .iter()
+        .flat_map(|check| check(world))
+        .collect();
+
+    let new = world
+        .resource_mut::<AssetExpectations>()
+        .reported
+        .unreported(&violations);
+
+    let mut world = DeferredWorld::from(world);
+    for violation in new {
+        report(&mut world, violation);
+    }
+}
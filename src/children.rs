@@ -0,0 +1,148 @@
+//! Expectations between parents and their children.
+
+use std::any::TypeId;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::hierarchy::{ChildOf, Children};
+use bevy_ecs::world::World;
+
+use crate::{has_component, ExpectComponents, ExpectationViolation, ViolationKind};
+
+/// An expectation on how many direct children of the entity have a component.
+///
+/// Generated by `#[expect_children(Slot, min = 9, max = 12)]`. Children are
+/// usually attached after the parent spawns, so these are checked once per
+/// frame rather than at insertion.
+pub struct ChildCountExpectation {
+    /// `TypeId` of the component counted on children.
+    pub component: TypeId,
+    /// Name of the counted component for error messages.
+    pub component_name: &'static str,
+    /// Minimum number of children with the component.
+    pub min: usize,
+    /// Maximum number of children with the component, if bounded.
+    pub max: Option<usize>,
+}
+
+/// Returns a violation for every direct child of `entity` missing one of `T`'s
+/// expected child components.
+///
+/// Children are usually attached after the parent spawns, so these are checked
+/// once per frame rather than at insertion.
+pub(crate) fn check_all_children<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expected = T::expected_on_all_children();
+    if expected.is_empty() {
+        return Vec::new();
+    }
+
+    let Some(children) = world.get::<Children>(entity) else {
+        return Vec::new();
+    };
+
+    let expecting = std::any::type_name::<T>();
+    let names = T::expected_on_all_children_names();
+    let mut violations = Vec::new();
+
+    for &child in children {
+        for (type_id, name) in expected.iter().zip(names.iter()) {
+            if !has_component(world, child, *type_id) {
+                violations.push(ExpectationViolation {
+                    entity,
+                    expecting,
+                    missing: name,
+                    message: format!(
+                        "{expecting} expects {name} on all children, but it was not found on child {child:?} of entity {entity:?}"
+                    ),
+                    kind: ViolationKind::Other,
+                    caller: None,
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+/// Returns a violation for each of `T`'s expected parent components missing
+/// from the parent of `entity`, or for each one if `entity` has no parent.
+pub(crate) fn check_parent<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expected = T::expected_on_parent();
+    if expected.is_empty() {
+        return Vec::new();
+    }
+
+    let expecting = std::any::type_name::<T>();
+    let names = T::expected_on_parent_names();
+    let parent = world.get::<ChildOf>(entity).map(ChildOf::parent);
+
+    expected
+        .iter()
+        .zip(names.iter())
+        .filter_map(|(type_id, name)| {
+            let message = match parent {
+                None => format!(
+                    "{expecting} expects a parent with {name}, but entity {entity:?} has no parent"
+                ),
+                Some(parent) if !has_component(world, parent, *type_id) => format!(
+                    "{expecting} expects {name} on its parent, but it was not found on parent {parent:?} of entity {entity:?}"
+                ),
+                Some(_) => return None,
+            };
+            Some(ExpectationViolation {
+                entity,
+                expecting,
+                missing: name,
+                message,
+                kind: ViolationKind::Other,
+                caller: None,
+            })
+        })
+        .collect()
+}
+
+/// Returns a violation for each of `T`'s child count expectations that the
+/// children of `entity` fall outside of.
+pub(crate) fn check_child_counts<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expecting = std::any::type_name::<T>();
+    let children = world.get::<Children>(entity);
+
+    T::expected_child_counts()
+        .iter()
+        .filter_map(|expectation| {
+            let name = expectation.component_name;
+            let count = children.map_or(0, |children| {
+                children
+                    .iter()
+                    .filter(|&&child| has_component(world, child, expectation.component))
+                    .count()
+            });
+
+            let bound = if count < expectation.min {
+                format!("at least {}", expectation.min)
+            } else {
+                let max = expectation.max.filter(|&max| count > max)?;
+                format!("at most {max}")
+            };
+
+            Some(ExpectationViolation {
+                entity,
+                expecting,
+                missing: name,
+                message: format!(
+                    "{expecting} expects {bound} children with {name} but found {count} on entity {entity:?}"
+                ),
+                kind: ViolationKind::Other,
+                caller: None,
+            })
+        })
+        .collect()
+}
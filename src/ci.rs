@@ -0,0 +1,84 @@
+//! Writing out and gating on collected failures in CI runs.
+
+use std::path::PathBuf;
+
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::resource::Resource;
+use bevy_ecs::system::Res;
+
+use crate::{PanicBehavior, ValidationReport};
+
+/// Writes the [`ValidationReport`] as JSON to this path at the end of each
+/// frame in which it changed, replacing the previous contents.
+///
+/// Pair it with [`FailureMode::Collect`](crate::FailureMode::Collect), which
+/// fills the report, so CI tooling can read every failure of a run.
+///
+/// ```rust,ignore
+/// app.insert_resource(JsonReport("target/expectations.json".into()));
+/// ```
+#[derive(Resource, Clone, Debug, PartialEq, Eq)]
+pub struct JsonReport(pub PathBuf);
+
+/// Fails the run once the [`ValidationReport`] holds more than this many
+/// failures.
+///
+/// Checked at the end of each frame, after the [`JsonReport`] is written, and
+/// brought down according to the [`PanicBehavior`]. A budget above zero lets a
+/// run tolerate known failures while the count is driven down.
+///
+/// ```rust,ignore
+/// app.insert_resource(ErrorBudget(5));
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorBudget(pub usize);
+
+/// Writes the report to the [`JsonReport`] path if either changed.
+#[allow(clippy::needless_pass_by_value)] // Bevy system params are passed by value
+pub(crate) fn write_json_report(target: Option<Res<JsonReport>>, report: Res<ValidationReport>) {
+    let Some(target) = target else {
+        return;
+    };
+    if !report.is_changed() && !target.is_changed() {
+        return;
+    }
+
+    if let Err(err) = std::fs::write(&target.0, report.to_json()) {
+        log::error!(
+            "failed to write the expectation report to {}: {err}",
+            target.0.display()
+        );
+    }
+}
+
+/// Fails once the report grows past the [`ErrorBudget`].
+#[allow(clippy::needless_pass_by_value)] // Bevy system params are passed by value
+pub(crate) fn enforce_error_budget(
+    budget: Option<Res<ErrorBudget>>,
+    report: Res<ValidationReport>,
+    behavior: Option<Res<PanicBehavior>>,
+) {
+    let Some(budget) = budget else {
+        return;
+    };
+    if !report.is_changed() || report.violations.len() <= budget.0 {
+        return;
+    }
+
+    let message = format!(
+        "{} expectation failures exceed the error budget of {}:\n{}",
+        report.violations.len(),
+        budget.0,
+        report
+            .violations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+    behavior
+        .as_deref()
+        .copied()
+        .unwrap_or_default()
+        .fail(&message);
+}
@@ -0,0 +1,55 @@
+//! Caching of resolved `ComponentId`s for the insertion hot path.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
+
+use bevy_ecs::component::{ComponentId, ComponentInfo, StorageType};
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::World;
+
+/// Expected component types resolved to their `ComponentId` and storage type.
+///
+/// Checks only hold a shared reference to the world, so entries are added
+/// behind a lock. Types not yet registered in the world are not cached and are
+/// resolved again on the next check, until they are registered.
+#[derive(Resource, Default)]
+pub(crate) struct ComponentIdCache(RwLock<HashMap<TypeId, (ComponentId, StorageType)>>);
+
+/// Resolves `type_id` to its `ComponentId` and storage type, or `None` if the
+/// type has not been registered in `world`.
+///
+/// Uses the plugin's cache when present, falling back to looking the type up in
+/// the world's components.
+pub(crate) fn resolve(world: &World, type_id: TypeId) -> Option<(ComponentId, StorageType)> {
+    let Some(cache) = world.get_resource::<ComponentIdCache>() else {
+        return lookup(world, type_id);
+    };
+
+    if let Some(resolved) = cache
+        .0
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&type_id)
+    {
+        return Some(*resolved);
+    }
+
+    let resolved = lookup(world, type_id)?;
+    cache
+        .0
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(type_id, resolved);
+    Some(resolved)
+}
+
+/// Looks `type_id` up in the world's components.
+fn lookup(world: &World, type_id: TypeId) -> Option<(ComponentId, StorageType)> {
+    let components = world.components();
+    let id = components.get_id(type_id)?;
+    let storage = components
+        .get_info(id)
+        .map_or(StorageType::Table, ComponentInfo::storage_type);
+    Some((id, storage))
+}
@@ -0,0 +1,87 @@
+//! Naming the components `#[expect_fn]` supplies, which only `TypeId`s identify.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use bevy_ecs::world::World;
+
+/// Names of computed components, resolved from the worlds they are registered in.
+///
+/// A `TypeId` names the same type in every world, so this is shared across them.
+fn names() -> &'static RwLock<HashMap<TypeId, &'static str>> {
+    static NAMES: OnceLock<RwLock<HashMap<TypeId, &'static str>>> = OnceLock::new();
+    NAMES.get_or_init(RwLock::default)
+}
+
+/// Records the names `world` has registered for any of `ids` not yet named.
+///
+/// Names are leaked, once per type.
+pub(crate) fn learn_names(world: &World, ids: &[TypeId]) {
+    let unknown: Vec<TypeId> = {
+        let names = names().read().unwrap_or_else(PoisonError::into_inner);
+        ids.iter()
+            .filter(|id| !names.contains_key(id))
+            .copied()
+            .collect()
+    };
+    if unknown.is_empty() {
+        return;
+    }
+
+    let components = world.components();
+    let learned: Vec<(TypeId, &'static str)> = unknown
+        .into_iter()
+        .filter_map(|id| {
+            let info = components.get_info(components.get_id(id)?)?;
+            Some((id, &*Box::leak(info.name().to_string().into_boxed_str())))
+        })
+        .collect();
+
+    names()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .extend(learned);
+}
+
+/// Returns `named` followed by the names of `computed`, falling back to the
+/// `TypeId` for components not registered in any world seen so far.
+///
+/// The slice is rebuilt, and leaked, only when more of `computed` have been
+/// named since it was last built, so at most once per component.
+pub(crate) fn with_computed_names(
+    expecting: TypeId,
+    named: &[&'static str],
+    computed: &[TypeId],
+) -> &'static [&'static str] {
+    type Built = (usize, &'static [&'static str]);
+    static BUILT: OnceLock<RwLock<HashMap<TypeId, Built>>> = OnceLock::new();
+    let built = BUILT.get_or_init(RwLock::default);
+
+    let known = names().read().unwrap_or_else(PoisonError::into_inner);
+    let resolved = computed.iter().filter(|id| known.contains_key(id)).count();
+
+    if let Some((count, slice)) = built
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&expecting)
+    {
+        if *count == resolved {
+            return slice;
+        }
+    }
+
+    let mut all = named.to_vec();
+    all.extend(computed.iter().map(|id| {
+        known
+            .get(id)
+            .copied()
+            .unwrap_or_else(|| Box::leak(format!("{id:?}").into_boxed_str()))
+    }));
+    let slice: &'static [&'static str] = all.leak();
+    built
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(expecting, (resolved, slice));
+    slice
+}
@@ -0,0 +1,346 @@
+//! Runtime configuration of how validation behaves.
+
+use std::any::TypeId;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::{DeferredWorld, World};
+
+use crate::ExpectationViolation;
+
+/// How a failed expectation is reported.
+///
+/// Insert this resource to change the behavior. When absent, [`FailureMode::Panic`]
+/// is used.
+///
+/// ```rust,ignore
+/// app.insert_resource(FailureMode::Warn);
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FailureMode {
+    /// Panic with a descriptive message.
+    #[default]
+    Panic,
+    /// Log a warning and continue.
+    Warn,
+    /// Log an error and continue, so every violation in a session is visible
+    /// without stopping the app.
+    Log,
+    /// Ignore failures.
+    ///
+    /// Unlike [`ValidationEnabled`], checks still run, so opt-in features such as
+    /// [`ValidationStats`](crate::ValidationStats) keep recording.
+    Silent,
+    /// Append each failure as a line to the file at this path and continue.
+    ///
+    /// Failures are buffered and written at the end of each frame, in the `Last`
+    /// schedule, so file IO stays off the insertion path.
+    File(PathBuf),
+    /// Warn the first time each pattern fails, and panic if it fails again.
+    ///
+    /// A pattern is an expecting component paired with the component it is
+    /// missing. This keeps adoption quiet while still catching violations that
+    /// were never fixed.
+    WarnThenPanic,
+    /// Warn the first time each pattern fails and ignore it afterwards.
+    ///
+    /// Suits advisory expectations, declared with `mode = "warn_once"`, that
+    /// would otherwise log again for every entity spawned. Call
+    /// [`clear_warn_cache`] to hear about each pattern again.
+    WarnOnce,
+    /// Log a warning and remove the expecting component from the entity.
+    ///
+    /// The entity stays alive, but loses the component that cannot function
+    /// without its dependencies, so downstream systems never see the invalid
+    /// state. The removal is queued as a command and applied once the insert
+    /// that triggered validation has finished.
+    RemoveComponent,
+    /// Append each failure to the [`ValidationReport`](crate::ValidationReport)
+    /// resource and continue.
+    ///
+    /// Suits test suites that spawn many entities and then assert on every
+    /// failure at once.
+    Collect,
+}
+
+/// Patterns that have already warned under [`FailureMode::WarnThenPanic`] or
+/// [`FailureMode::WarnOnce`].
+#[derive(Resource, Default)]
+pub(crate) struct WarnedPatterns(HashSet<(&'static str, &'static str)>);
+
+impl WarnedPatterns {
+    /// Records the pattern, returning true if it had not been seen before.
+    pub(crate) fn first_occurrence(
+        &mut self,
+        expecting: &'static str,
+        missing: &'static str,
+    ) -> bool {
+        self.0.insert((expecting, missing))
+    }
+
+    /// Returns true if the pattern has already warned.
+    pub(crate) fn contains(&self, expecting: &'static str, missing: &'static str) -> bool {
+        self.0.contains(&(expecting, missing))
+    }
+}
+
+/// How [`FailureMode::Panic`] and repeated [`FailureMode::WarnThenPanic`] failures bring the app down.
+///
+/// Insert this resource to change the behavior. When absent,
+/// [`PanicBehavior::Unwind`] is used.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub enum PanicBehavior {
+    /// Call `panic!`, unwinding the stack so the failure can be caught in tests.
+    #[default]
+    Unwind,
+    /// Call [`std::process::abort`] immediately.
+    ///
+    /// Use this for servers that must crash consistently, or builds with
+    /// `panic = "abort"` where unwinding is unavailable anyway. Aborting skips
+    /// destructors and panic hooks, so the failure message is logged as an
+    /// error first.
+    Abort,
+    /// Call a custom function with the failure message instead of panicking.
+    ///
+    /// Validation continues if the function returns.
+    Custom(fn(&str)),
+}
+
+impl PanicBehavior {
+    /// Brings the app down with `message`.
+    pub(crate) fn fail(self, message: &str) {
+        match self {
+            PanicBehavior::Unwind => panic!("{message}"),
+            PanicBehavior::Abort => {
+                log::error!("{message}");
+                std::process::abort();
+            }
+            PanicBehavior::Custom(handler) => handler(message),
+        }
+    }
+}
+
+/// Toggles validation at runtime without removing the plugin.
+///
+/// When absent, validation is enabled.
+///
+/// ```rust,ignore
+/// app.insert_resource(ValidationEnabled(false));
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationEnabled(pub bool);
+
+impl Default for ValidationEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Expecting types whose validation is temporarily suppressed.
+///
+/// Insert this resource and suppress types to stop validating them, for
+/// example while a cutscene spawns half-built entities, without affecting any
+/// other type.
+///
+/// ```rust,ignore
+/// app.world_mut()
+///     .get_resource_or_init::<ExpectationOverrides>()
+///     .suppress::<PhysicsBody>();
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct ExpectationOverrides(HashSet<TypeId>);
+
+impl ExpectationOverrides {
+    /// Stops validating `T`'s expectations.
+    pub fn suppress<T: Component>(&mut self) {
+        self.0.insert(TypeId::of::<T>());
+    }
+
+    /// Resumes validating `T`'s expectations.
+    pub fn unsuppress<T: Component>(&mut self) {
+        self.0.remove(&TypeId::of::<T>());
+    }
+
+    /// Returns true if validation of the type with this `TypeId` is suppressed.
+    #[must_use]
+    pub fn is_suppressed(&self, type_id: TypeId) -> bool {
+        self.0.contains(&type_id)
+    }
+}
+
+/// Returns true if validation of the expecting type with this `TypeId` is
+/// suppressed by [`ExpectationOverrides`].
+pub(crate) fn is_suppressed(world: &World, type_id: TypeId) -> bool {
+    world
+        .get_resource::<ExpectationOverrides>()
+        .is_some_and(|overrides| overrides.is_suppressed(type_id))
+}
+
+/// Marker resource present when expecting types defined by Bevy itself are not validated.
+#[derive(Resource)]
+pub(crate) struct SkipBevyInternal;
+
+/// Marker resource present while hooks are registered on `on_insert` instead of `on_add`.
+#[derive(Resource)]
+pub(crate) struct ValidateOnInsert;
+
+/// Bevy's own crates. Third-party crates are often named `bevy_*` too, so the
+/// prefix alone does not tell them apart.
+const BEVY_CRATES: &[&str] = &[
+    "bevy",
+    "bevy_a11y",
+    "bevy_android",
+    "bevy_animation",
+    "bevy_anti_alias",
+    "bevy_app",
+    "bevy_asset",
+    "bevy_audio",
+    "bevy_camera",
+    "bevy_camera_controller",
+    "bevy_color",
+    "bevy_core_pipeline",
+    "bevy_dev_tools",
+    "bevy_diagnostic",
+    "bevy_ecs",
+    "bevy_feathers",
+    "bevy_gilrs",
+    "bevy_gizmos",
+    "bevy_gizmos_render",
+    "bevy_gltf",
+    "bevy_image",
+    "bevy_input",
+    "bevy_input_focus",
+    "bevy_internal",
+    "bevy_light",
+    "bevy_log",
+    "bevy_math",
+    "bevy_mesh",
+    "bevy_pbr",
+    "bevy_picking",
+    "bevy_platform",
+    "bevy_post_process",
+    "bevy_reflect",
+    "bevy_remote",
+    "bevy_render",
+    "bevy_scene",
+    "bevy_shader",
+    "bevy_solari",
+    "bevy_sprite",
+    "bevy_sprite_render",
+    "bevy_state",
+    "bevy_tasks",
+    "bevy_text",
+    "bevy_time",
+    "bevy_transform",
+    "bevy_ui",
+    "bevy_ui_render",
+    "bevy_ui_widgets",
+    "bevy_utils",
+    "bevy_window",
+    "bevy_winit",
+];
+
+/// Returns true if `type_name` belongs to one of Bevy's crates rather than user code.
+pub(crate) fn is_bevy_internal(type_name: &str) -> bool {
+    type_name
+        .split_once("::")
+        .is_some_and(|(krate, _)| BEVY_CRATES.contains(&krate))
+}
+
+type GateFn = Box<dyn Fn(&DeferredWorld, Entity) -> bool + Send + Sync>;
+
+/// Decides per insert whether validation runs for an entity.
+///
+/// When present, the hook calls the gate before validating and skips the
+/// entity if it returns false. Gate on anything: tags, time, random sampling.
+///
+/// ```rust,ignore
+/// app.insert_resource(ValidationGate::new(|world, entity| {
+///     !world.entity(entity).contains::<Prototype>()
+/// }));
+/// ```
+#[derive(Resource)]
+pub struct ValidationGate(GateFn);
+
+impl ValidationGate {
+    /// Creates a gate that validates an entity only when `gate` returns true.
+    pub fn new(gate: impl Fn(&DeferredWorld, Entity) -> bool + Send + Sync + 'static) -> Self {
+        Self(Box::new(gate))
+    }
+
+    /// Returns true if validation should run for `entity`.
+    #[must_use]
+    pub fn should_validate(&self, world: &DeferredWorld, entity: Entity) -> bool {
+        (self.0)(world, entity)
+    }
+}
+
+type HandlerFn = Box<dyn Fn(&ExpectationViolation) + Send + Sync>;
+
+/// Takes over reporting failed expectations from the [`FailureMode`].
+///
+/// When present, every violation is passed to the handler instead of being
+/// panicked on, logged or collected, so it can be forwarded to a crash
+/// reporter. The handler may panic itself. Set it with
+/// [`ExpectedComponentsPlugin::with_handler`](crate::ExpectedComponentsPlugin::with_handler),
+/// or insert it directly:
+///
+/// ```rust,ignore
+/// app.insert_resource(ViolationHandler::new(|violation| {
+///     crash_reporter::send(&violation.message);
+/// }));
+/// ```
+#[derive(Resource)]
+pub struct ViolationHandler(HandlerFn);
+
+impl ViolationHandler {
+    /// Creates a handler that reports each violation by calling `handler`.
+    pub fn new(handler: impl Fn(&ExpectationViolation) + Send + Sync + 'static) -> Self {
+        Self(Box::new(handler))
+    }
+
+    /// Reports `violation` through the handler.
+    pub fn handle(&self, violation: &ExpectationViolation) {
+        (self.0)(violation);
+    }
+}
+
+/// Returns the failure mode currently in effect.
+#[must_use]
+pub fn current_failure_mode(world: &World) -> FailureMode {
+    world
+        .get_resource::<FailureMode>()
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Forgets which patterns have already warned, so [`FailureMode::WarnOnce`]
+/// warns about each again and [`FailureMode::WarnThenPanic`] warns before
+/// panicking again.
+pub fn clear_warn_cache(world: &mut World) {
+    if let Some(mut warned) = world.get_resource_mut::<WarnedPatterns>() {
+        warned.0.clear();
+    }
+}
+
+/// Returns true if validation is currently enabled.
+#[must_use]
+pub fn validation_enabled(world: &World) -> bool {
+    world
+        .get_resource::<ValidationEnabled>()
+        .copied()
+        .unwrap_or_default()
+        .0
+}
+
+/// Returns the panic behavior currently in effect.
+#[must_use]
+pub fn current_panic_behavior(world: &World) -> PanicBehavior {
+    world
+        .get_resource::<PanicBehavior>()
+        .copied()
+        .unwrap_or_default()
+}
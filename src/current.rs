@@ -0,0 +1,35 @@
+//! Validating the entity an editor or builder is currently working on.
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::World;
+
+use crate::validation::entity_violations;
+use crate::ExpectationViolation;
+
+/// The entity that builder or editor code is currently operating on.
+///
+/// ```rust,ignore
+/// world.insert_resource(CurrentEntity(selected));
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurrentEntity(pub Entity);
+
+/// Validates the entity held in [`CurrentEntity`] against every registered expectation.
+///
+/// Returns no violations if the resource is absent. Like
+/// [`validate_scene_root`](crate::validate_scene_root), this works with or without
+/// the plugin and never panics.
+///
+/// ```rust,ignore
+/// for violation in expect_current(world) {
+///     editor.show_error(&violation);
+/// }
+/// ```
+#[must_use]
+pub fn expect_current(world: &World) -> Vec<ExpectationViolation> {
+    world
+        .get_resource::<CurrentEntity>()
+        .map(|current| entity_violations(world, current.0))
+        .unwrap_or_default()
+}
@@ -0,0 +1,40 @@
+//! Publishing violation counts through Bevy's diagnostics.
+
+use bevy_diagnostic::{DiagnosticPath, Diagnostics};
+use bevy_ecs::resource::Resource;
+use bevy_ecs::system::ResMut;
+use bevy_ecs::world::DeferredWorld;
+
+/// Diagnostic measuring how many violations were reported each frame.
+///
+/// The plugin registers it when this crate's `diagnostics` feature is enabled.
+/// Read it like any other diagnostic, for example in an overlay or through
+/// `LogDiagnosticsPlugin`:
+///
+/// ```rust,ignore
+/// let violations = store
+///     .get(&VIOLATIONS_DIAGNOSTIC)
+///     .and_then(Diagnostic::value);
+/// ```
+pub const VIOLATIONS_DIAGNOSTIC: DiagnosticPath =
+    DiagnosticPath::const_new("expected_components/violations");
+
+/// Violations reported since the last measurement.
+#[derive(Resource, Default)]
+pub(crate) struct FrameViolations(u32);
+
+/// Counts a reported violation towards this frame's measurement.
+pub(crate) fn count_violation(world: &mut DeferredWorld) {
+    if let Some(mut violations) = world.get_resource_mut::<FrameViolations>() {
+        violations.0 = violations.0.saturating_add(1);
+    }
+}
+
+/// Records this frame's violation count as a measurement and starts counting anew.
+pub(crate) fn measure_violations(
+    mut violations: ResMut<FrameViolations>,
+    mut diagnostics: Diagnostics,
+) {
+    let count = std::mem::take(&mut violations.0);
+    diagnostics.add_measurement(&VIOLATIONS_DIAGNOSTIC, || f64::from(count));
+}
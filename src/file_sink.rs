@@ -0,0 +1,57 @@
+//! Buffered writing of failures to a file for [`FailureMode::File`].
+
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use bevy_ecs::resource::Resource;
+use bevy_ecs::system::{Res, ResMut};
+use bevy_ecs::world::DeferredWorld;
+
+use crate::FailureMode;
+
+/// Failure messages waiting to be written to the failure file.
+#[derive(Resource, Default)]
+pub(crate) struct PendingFailures(Vec<String>);
+
+/// Queues a failure message for the next flush.
+pub(crate) fn buffer(world: &mut DeferredWorld, message: &str) {
+    match world.get_resource_mut::<PendingFailures>() {
+        // One line per failure, so multi-line messages are joined
+        Some(mut pending) => pending.0.push(message.replace('\n', "; ")),
+        None => log::warn!("{message}"),
+    }
+}
+
+/// Appends buffered failures to the configured file.
+#[allow(clippy::needless_pass_by_value)] // Bevy system params are passed by value
+pub(crate) fn flush_pending_failures(
+    mode: Option<Res<FailureMode>>,
+    mut pending: ResMut<PendingFailures>,
+) {
+    if pending.0.is_empty() {
+        return;
+    }
+
+    let lines = std::mem::take(&mut pending.0);
+    let Some(FailureMode::File(path)) = mode.as_deref() else {
+        return;
+    };
+
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| {
+            for line in &lines {
+                writeln!(file, "{line}")?;
+            }
+            Ok(())
+        });
+
+    if let Err(err) = result {
+        log::error!(
+            "failed to write expectation failures to {}: {err}",
+            path.display()
+        );
+    }
+}
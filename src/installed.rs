@@ -0,0 +1,40 @@
+//! Detecting expectations used in a process where the plugin was never built.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Process-wide rather than per-thread: systems run on task pool threads, which
+// never build the plugin themselves.
+static PLUGIN_BUILT: AtomicBool = AtomicBool::new(false);
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Records that [`ExpectedComponentsPlugin`](crate::ExpectedComponentsPlugin)
+/// was built.
+pub(crate) fn mark_plugin_built() {
+    PLUGIN_BUILT.store(true, Ordering::Relaxed);
+}
+
+/// Returns true if [`ExpectedComponentsPlugin`](crate::ExpectedComponentsPlugin)
+/// has been built on any thread.
+#[must_use]
+pub fn plugin_installed() -> bool {
+    PLUGIN_BUILT.load(Ordering::Relaxed)
+}
+
+/// Warns once per process if expectations are checked before the plugin was
+/// ever built, returning true if the warning was emitted by this call.
+///
+/// Nothing in this crate runs when a component is inserted unless the plugin has
+/// installed hooks, so a forgotten plugin can't be detected at insertion. This
+/// is called from code paths that work without the plugin, such as the inline
+/// `debug_assert_<type>_expectations` functions, where a missing plugin is
+/// likely an oversight.
+pub(crate) fn warn_if_plugin_missing(expecting: &str) -> bool {
+    if plugin_installed() || WARNED.swap(true, Ordering::Relaxed) {
+        return false;
+    }
+
+    log::warn!(
+        "{expecting} has expectations but ExpectedComponentsPlugin was not added, so they are not validated on insert"
+    );
+    true
+}
@@ -0,0 +1,393 @@
+//! Read-only views of the expectation registry for tooling.
+
+use std::any::TypeId;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::{registry, ExpectComponents, ExpectRegistration};
+
+/// Returns every registration collected by `inventory` or submitted through
+/// `register_all_expectations!` that describes its type.
+///
+/// Deprecated hooks-only registrations carry no metadata, so are left out.
+pub(crate) fn collected() -> impl Iterator<Item = &'static ExpectRegistration> {
+    registry::collected_registrations().filter(|registration| !registration.hooks_only)
+}
+
+/// Returns true if `T`'s registration was collected by `inventory`.
+///
+/// A registration can be silently dropped, for example when the linker strips
+/// the object file that submitted it, leaving validation for `T` doing nothing.
+/// Assert this at startup or in tests for expectations that matter.
+///
+/// ```rust,ignore
+/// assert!(is_registered::<PhysicsBody>());
+/// ```
+#[must_use]
+pub fn is_registered<T: ExpectComponents>() -> bool {
+    collected().any(|registration| registration.type_id() == TypeId::of::<T>())
+}
+
+/// An expecting type collected by `inventory` and the components it expects.
+#[derive(Clone, Copy, Debug)]
+pub struct RegistrationInfo {
+    /// Type name of the expecting component.
+    pub type_name: &'static str,
+    /// Names of the components it expects, in declaration order.
+    pub expected: &'static [&'static str],
+}
+
+/// Returns every expecting type collected by `inventory`, in no particular
+/// order.
+///
+/// ```rust,ignore
+/// for info in all_registrations() {
+///     info!("{} expects {}", info.type_name, info.expected.join(", "));
+/// }
+/// ```
+pub fn all_registrations() -> impl Iterator<Item = RegistrationInfo> {
+    collected().map(|registration| RegistrationInfo {
+        type_name: registration.type_name(),
+        expected: registration.expected_component_names(),
+    })
+}
+
+/// How settled an expectation is, as declared by the type's author.
+///
+/// Set with `#[expects(Position, stability = "experimental")]`. Stability is
+/// metadata for tooling and does not change how expectations are validated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stability {
+    /// The expectation is part of the type's contract and will not change lightly.
+    Stable,
+    /// The expectation may change or be removed in a future version.
+    Experimental,
+}
+
+/// Returns the declared stability of the expectation that `expecting` has on
+/// `expected`, both given as type names.
+///
+/// Returns `None` if the type is not registered, does not expect the component,
+/// or did not annotate the expectation with a stability.
+#[must_use]
+pub fn expectation_stability(expecting: &str, expected: &str) -> Option<Stability> {
+    collected()
+        .find(|registration| registration.type_name() == expecting)?
+        .expected_stability()
+        .iter()
+        .find(|(name, _)| *name == expected)
+        .map(|(_, stability)| *stability)
+}
+
+/// Returns a stable textual description of every registered expectation.
+///
+/// Types are sorted by name, each followed by one indented line per expectation
+/// in declaration order. The output is suitable for snapshot tests, so changes
+/// to expectations show up as a diff in review.
+///
+/// ```text
+/// my_game::PhysicsBody
+///     expects my_game::Position
+///     expects my_game::Velocity
+/// ```
+#[must_use]
+pub fn registry_snapshot() -> String {
+    let mut registrations: Vec<&ExpectRegistration> = collected().collect();
+    registrations.sort_by_key(|registration| registration.type_name());
+
+    let mut snapshot = String::new();
+    for registration in registrations {
+        let _ = writeln!(snapshot, "{}", registration.type_name());
+        for line in registration.describe_expectations() {
+            let _ = writeln!(snapshot, "    {line}");
+        }
+    }
+    snapshot
+}
+
+/// Returns each registered expecting type's name with the names of the
+/// components it expects, sorted by expecting type name.
+///
+/// This reads registration metadata only, so it needs no world.
+/// [`registry_to_dot`] renders the same graph for Graphviz.
+///
+/// ```rust,ignore
+/// for (expecting, expected) in expectation_graph() {
+///     println!("{expecting} -> {}", expected.join(", "));
+/// }
+/// ```
+#[must_use]
+pub fn expectation_graph() -> Vec<(&'static str, Vec<&'static str>)> {
+    let mut graph: Vec<(&'static str, Vec<&'static str>)> = collected()
+        .map(|registration| {
+            (
+                registration.type_name(),
+                registration.expected_component_names().to_vec(),
+            )
+        })
+        .collect();
+    graph.sort_by_key(|(expecting, _)| *expecting);
+    graph
+}
+
+/// Renders the registry as a Graphviz DOT graph of component dependencies.
+///
+/// Every expecting and expected component is a node, with an edge from each
+/// expecting component to each component it expects. Output is sorted, so it is
+/// stable across runs.
+///
+/// ```text
+/// digraph expectations {
+///     "my_game::PhysicsBody";
+///     "my_game::Position";
+///     "my_game::PhysicsBody" -> "my_game::Position";
+/// }
+/// ```
+#[must_use]
+pub fn registry_to_dot() -> String {
+    let mut nodes = BTreeSet::new();
+    let mut edges = BTreeSet::new();
+
+    for (expecting, expected) in expectation_graph() {
+        nodes.insert(expecting);
+        for expected in expected {
+            nodes.insert(expected);
+            edges.insert((expecting, expected));
+        }
+    }
+
+    let mut dot = String::from("digraph expectations {\n");
+    for node in nodes {
+        let _ = writeln!(dot, "    {node:?};");
+    }
+    for (expecting, expected) in edges {
+        let _ = writeln!(dot, "    {expecting:?} -> {expected:?};");
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Returns the cycles among `registrations`' expectations, each as the type
+/// names along the cycle, starting and ending with the same type.
+///
+/// An entity with any component in a cycle is only valid if every other
+/// component in the cycle is inserted in the same operation, which is usually a
+/// design mistake. Each set of types is reported once, and the output is
+/// sorted so it is stable across runs.
+pub(crate) fn expectation_cycles<'a>(
+    registrations: impl IntoIterator<Item = &'a ExpectRegistration>,
+) -> Vec<Vec<&'static str>> {
+    let mut registrations: Vec<&ExpectRegistration> = registrations.into_iter().collect();
+    registrations.sort_by_key(|registration| registration.type_name());
+
+    let index: HashMap<TypeId, usize> = registrations
+        .iter()
+        .enumerate()
+        .map(|(index, registration)| (registration.type_id(), index))
+        .collect();
+    let edges: Vec<Vec<usize>> = registrations
+        .iter()
+        .map(|registration| {
+            registration
+                .expected_components()
+                .iter()
+                .filter_map(|type_id| index.get(type_id).copied())
+                .collect()
+        })
+        .collect();
+
+    let mut search = CycleSearch {
+        edges: &edges,
+        visited: vec![false; edges.len()],
+        path: Vec::new(),
+        found: Vec::new(),
+    };
+    for node in 0..edges.len() {
+        search.visit(node);
+    }
+
+    let mut seen = HashSet::new();
+    search
+        .found
+        .into_iter()
+        .filter(|cycle| {
+            let mut members = cycle.clone();
+            members.sort_unstable();
+            seen.insert(members)
+        })
+        .map(|cycle| {
+            let mut names: Vec<&'static str> = cycle
+                .iter()
+                .map(|&node| registrations[node].type_name())
+                .collect();
+            names.push(names[0]);
+            names
+        })
+        .collect()
+}
+
+/// Depth-first search recording every cycle closed by an edge back into the
+/// current path.
+struct CycleSearch<'a> {
+    edges: &'a [Vec<usize>],
+    visited: Vec<bool>,
+    path: Vec<usize>,
+    found: Vec<Vec<usize>>,
+}
+
+impl CycleSearch<'_> {
+    fn visit(&mut self, node: usize) {
+        if let Some(start) = self.path.iter().position(|&on_path| on_path == node) {
+            self.found.push(self.path[start..].to_vec());
+            return;
+        }
+        if self.visited[node] {
+            return;
+        }
+
+        self.visited[node] = true;
+        self.path.push(node);
+        for &next in &self.edges[node] {
+            self.visit(next);
+        }
+        self.path.pop();
+    }
+}
+
+/// Describes each of `T`'s expectations on its own line.
+pub(crate) fn describe_expectations_of<T: ExpectComponents>() -> Vec<String> {
+    let mut lines: Vec<String> = T::expected_component_names()
+        .iter()
+        .map(|name| {
+            match T::expected_stability()
+                .iter()
+                .find(|(annotated, _)| annotated == name)
+            {
+                Some((_, Stability::Stable)) => format!("expects {name} (stable)"),
+                Some((_, Stability::Experimental)) => format!("expects {name} (experimental)"),
+                None => format!("expects {name}"),
+            }
+        })
+        .collect();
+
+    lines.extend(
+        T::expected_unless_present_names()
+            .iter()
+            .map(|(absent, required)| format!("expects {required} unless {absent}")),
+    );
+
+    lines.extend(
+        T::expected_if_names()
+            .iter()
+            .map(|(guard, required)| format!("expects {required} when {guard} is present")),
+    );
+
+    lines.extend(
+        T::expected_any_names()
+            .iter()
+            .map(|names| format!("expects at least one of {}", names.join(", "))),
+    );
+
+    lines.extend(
+        T::expected_one_of_names()
+            .iter()
+            .map(|names| format!("expects exactly one of {}", names.join(", "))),
+    );
+
+    lines.extend(
+        T::expected_resource_names()
+            .iter()
+            .map(|name| format!("expects resource {name}")),
+    );
+
+    lines.extend(
+        T::forbidden_component_names()
+            .iter()
+            .map(|name| format!("forbids {name}")),
+    );
+
+    lines.extend(
+        T::expected_prior_names()
+            .iter()
+            .map(|name| format!("expects {name} added before it")),
+    );
+
+    lines.extend(
+        T::expected_non_default()
+            .iter()
+            .map(|expectation| format!("expects {} not to be default", expectation.component)),
+    );
+
+    lines.extend(
+        T::expected_predicates()
+            .iter()
+            .map(|expectation| format!("expects {} to satisfy a predicate", expectation.component)),
+    );
+
+    lines.extend(T::expected_by_variant().iter().map(|expectation| {
+        format!(
+            "expects {} when {}",
+            expectation.component_name, expectation.variant
+        )
+    }));
+
+    lines.extend(describe_relationships_of::<T>());
+
+    if T::include_required_components() {
+        lines.push("counts required components as present".to_owned());
+    }
+
+    lines
+}
+
+/// Describes `T`'s expectations on related entities, such as relationship targets and children.
+fn describe_relationships_of<T: ExpectComponents>() -> Vec<String> {
+    let mut lines: Vec<String> = T::expected_on_both_ends()
+        .iter()
+        .map(|expectation| {
+            format!(
+                "expects {} on both ends of {}",
+                expectation.expected_name, expectation.relationship
+            )
+        })
+        .collect();
+
+    lines.extend(T::expected_on_related().iter().map(|expectation| {
+        format!(
+            "expects {} on its {} target",
+            expectation.expected_name, expectation.relationship
+        )
+    }));
+
+    lines.extend(T::expected_incoming().iter().map(|expectation| {
+        format!(
+            "expects at least {} incoming {}",
+            expectation.min, expectation.relationship
+        )
+    }));
+
+    lines.extend(
+        T::expected_on_all_children_names()
+            .iter()
+            .map(|name| format!("expects {name} on all children")),
+    );
+
+    lines.extend(T::expected_child_counts().iter().map(|expectation| {
+        let max = expectation
+            .max
+            .map(|max| format!(" and at most {max}"))
+            .unwrap_or_default();
+        format!(
+            "expects at least {}{max} children with {}",
+            expectation.min, expectation.component_name
+        )
+    }));
+
+    lines.extend(
+        T::expected_on_parent_names()
+            .iter()
+            .map(|name| format!("expects {name} on its parent")),
+    );
+
+    lines
+}
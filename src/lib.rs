@@ -78,9 +78,10 @@
 //!
 //! ## Limitations
 //!
-//! **Validates insertion only, not removal.** If you later remove an expected component
-//! from an entity, no error occurs. This keeps the implementation simple and covers
-//! the main use case: catching mistakes at spawn time.
+//! **Removal protection covers `#[expects]` only.** Removing an expected component
+//! while the component expecting it stays on the entity is reported, but despawning
+//! or removing both together is fine. Other kinds of expectations, such as
+//! `#[expect_if]` or relationships, are checked on insertion only.
 //!
 //! ## Future of This Crate
 //!
@@ -91,19 +92,122 @@
 //! - [Issue #18717: Support required components which have no sensible default](https://github.com/bevyengine/bevy/issues/18717)
 //! - Archetype invariants (future Bevy feature)
 
+// Without `validate` the plugin installs nothing, so the systems it would add
+// are never referenced and are left out of the build
+#![cfg_attr(not(feature = "validate"), allow(dead_code))]
+
+mod add_order;
+mod asset;
+mod children;
+mod ci;
+mod component_ids;
+mod computed;
+mod config;
+mod current;
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+mod file_sink;
+mod installed;
+mod introspection;
+mod lookup;
+mod named;
+mod non_default;
+mod predicate;
+mod presets;
+mod rate_limit;
+mod recovery;
+mod registry;
+mod related;
+mod removal;
+mod reported;
+mod scene;
+mod scheduled;
+mod single;
+mod spawn;
+mod stats;
+mod status;
+mod tags;
+#[cfg(feature = "test-utils")]
+mod test_utils;
+mod tracking;
+mod validation;
+mod validator;
+mod variant;
+mod verbose;
+mod violation;
+
 use std::any::TypeId;
+use std::collections::HashSet;
+use std::sync::{Mutex, PoisonError};
 
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Last, Plugin};
 use bevy_ecs::component::Component;
-use bevy_ecs::lifecycle::HookContext;
-use bevy_ecs::world::{DeferredWorld, World};
+use bevy_ecs::entity::Entity;
+use bevy_ecs::resource::Resource;
+use bevy_ecs::schedule::{
+    ApplyDeferred, BoxedCondition, InternedScheduleLabel, IntoScheduleConfigs, ScheduleLabel,
+    SystemCondition,
+};
+use bevy_ecs::system::IntoSystem;
+use bevy_ecs::world::World;
 
-// Re-export for macro use
+// Re-exports for macro use
+#[doc(hidden)]
+pub use bevy_ecs::world::{EntityRef as __EntityRef, World as __World};
 #[doc(hidden)]
 pub use inventory;
 
 // Re-export derive macro
-pub use bevy_expected_components_macros::ExpectComponents;
+pub use bevy_expected_components_macros::{register_all_expectations, ExpectComponents};
+
+pub use add_order::{track_add_order, AddOrderTracker};
+pub use asset::{register_asset_expectation, DeclaredComponents};
+pub use children::ChildCountExpectation;
+pub use ci::{ErrorBudget, JsonReport};
+pub use config::{
+    clear_warn_cache, current_failure_mode, current_panic_behavior, validation_enabled,
+    ExpectationOverrides, FailureMode, PanicBehavior, ValidationEnabled, ValidationGate,
+    ViolationHandler,
+};
+pub use current::{expect_current, CurrentEntity};
+#[cfg(feature = "diagnostics")]
+pub use diagnostics::VIOLATIONS_DIAGNOSTIC;
+pub use installed::plugin_installed;
+pub use introspection::{
+    all_registrations, expectation_graph, expectation_stability, is_registered, registry_snapshot,
+    registry_to_dot, RegistrationInfo, Stability,
+};
+pub use lookup::ExpectationLookup;
+pub use named::register_named_expectation;
+pub use non_default::{is_default_value, was_mutated, NonDefaultExpectation};
+pub use predicate::PredicateExpectation;
+pub use rate_limit::ViolationRateLimit;
+pub use recovery::register_recovery;
+pub use registry::ExpectationRegistry;
+pub use related::{
+    incoming_relationship_count, relationship_target, IncomingExpectation, RelatedExpectation,
+};
+#[cfg(feature = "scene")]
+pub use scene::SceneValidationReport;
+pub use scene::{validate_scene_root, validate_scene_world};
+pub use scheduled::{
+    validation_system, CompletionDeadline, ValidationSystems, ValidationTimeBudget,
+};
+pub use single::{register_single_expectation, SingletonGracePeriod};
+pub use spawn::{validate_bundle, ExpectSpawnExt};
+pub use stats::{validation_stats_by_type, TypeStats, ValidationStats};
+pub use status::{ExpectationStatus, ExpectationStatusItem};
+pub use validation::{assert_entity_expectations, validate_entity, validate_entity_in};
+pub use validator::ExpectationValidator;
+pub use variant::VariantExpectation;
+pub use verbose::{FailureBacktraces, VerboseFailures};
+pub use violation::{ExpectationReport, ExpectationViolation, ValidationReport, ViolationKind};
+
+pub use tags::{EnabledTags, ExpectationGroups};
+#[cfg(feature = "test-utils")]
+pub use test_utils::{assert_expectation_violated, assert_expectations_satisfied};
+pub use tracking::{never_spawned_types, SpawnTracker};
+use validation::{check_expectations, has_component, report, validate_expected};
 
 /// Prelude module for convenient imports.
 ///
@@ -112,13 +216,14 @@ pub use bevy_expected_components_macros::ExpectComponents;
 /// ```
 pub mod prelude {
     pub use crate::ExpectComponents;
+    pub use crate::ExpectedComponentsConfig;
     pub use crate::ExpectedComponentsPlugin;
+    pub use crate::FailureMode;
 }
 
 /// Trait implemented by components that expect other components to be present.
 ///
-/// This trait is automatically implemented by the `#[derive(ExpectComponents)]` macro.
-/// You should not need to implement it manually.
+/// This trait is usually implemented by the `#[derive(ExpectComponents)]` macro.
 ///
 /// # Example
 ///
@@ -127,43 +232,573 @@ pub mod prelude {
 /// #[expects(Transform, Velocity)]
 /// struct PhysicsBody;
 /// ```
+///
+/// # Manual Implementation
+///
+/// If you prefer to avoid proc macros, implement the trait by hand using the
+/// associated consts and submit an [`ExpectRegistration`] so the plugin picks it up:
+///
+/// ```rust,ignore
+/// use std::any::TypeId;
+/// use bevy_expected_components::{ExpectComponents, ExpectRegistration};
+///
+/// #[derive(Component)]
+/// struct PhysicsBody;
+///
+/// impl ExpectComponents for PhysicsBody {
+///     const EXPECTED: &'static [TypeId] = &[TypeId::of::<Transform>()];
+///     const EXPECTED_NAMES: &'static [&'static str] = &["Transform"];
+/// }
+///
+/// bevy_expected_components::inventory::submit!(ExpectRegistration::of::<PhysicsBody>());
+/// ```
 pub trait ExpectComponents: Component {
+    /// `TypeId`s of expected components, for hand-written implementations.
+    const EXPECTED: &'static [TypeId] = &[];
+
+    /// Names of expected components, in the same order as [`Self::EXPECTED`].
+    const EXPECTED_NAMES: &'static [&'static str] = &[];
+
     /// Returns the `TypeId`s of expected components.
-    fn expected_components() -> &'static [TypeId];
+    #[must_use]
+    fn expected_components() -> &'static [TypeId] {
+        Self::EXPECTED
+    }
 
     /// Returns human-readable names of expected components for error messages.
-    fn expected_component_names() -> &'static [&'static str];
+    #[must_use]
+    fn expected_component_names() -> &'static [&'static str] {
+        Self::EXPECTED_NAMES
+    }
+
+    /// Returns the expected components supplied by `#[expect_fn]`, which are
+    /// also in [`Self::expected_components`]. Only their `TypeId`s are known, so
+    /// they are named as registered in the world.
+    #[must_use]
+    fn computed_components() -> &'static [TypeId] {
+        &[]
+    }
+
+    /// Returns `(absent, required)` pairs: if `absent` is not on the entity,
+    /// `required` must be.
+    #[must_use]
+    fn expected_unless_present() -> &'static [(TypeId, TypeId)] {
+        &[]
+    }
+
+    /// Returns names matching [`Self::expected_unless_present`] for error messages.
+    #[must_use]
+    fn expected_unless_present_names() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Returns `(guard, required)` pairs: if `guard` is on the entity,
+    /// `required` must be too.
+    #[must_use]
+    fn expected_if() -> &'static [(TypeId, TypeId)] {
+        &[]
+    }
+
+    /// Returns names matching [`Self::expected_if`] for error messages.
+    #[must_use]
+    fn expected_if_names() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Returns groups of components of which at least one must be on the entity.
+    #[must_use]
+    fn expected_any() -> &'static [&'static [TypeId]] {
+        &[]
+    }
+
+    /// Returns names matching [`Self::expected_any`] for error messages.
+    #[must_use]
+    fn expected_any_names() -> &'static [&'static [&'static str]] {
+        &[]
+    }
+
+    /// Returns groups of components of which exactly one must be on the entity.
+    #[must_use]
+    fn expected_one_of() -> &'static [&'static [TypeId]] {
+        &[]
+    }
+
+    /// Returns names matching [`Self::expected_one_of`] for error messages.
+    #[must_use]
+    fn expected_one_of_names() -> &'static [&'static [&'static str]] {
+        &[]
+    }
+
+    /// Returns the `TypeId`s of components that must not be on the entity.
+    #[must_use]
+    fn forbidden_components() -> &'static [TypeId] {
+        &[]
+    }
+
+    /// Returns names matching [`Self::forbidden_components`] for error messages.
+    #[must_use]
+    fn forbidden_component_names() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the `TypeId`s of resources that must exist in the world when
+    /// the component is added.
+    #[must_use]
+    fn expected_resources() -> &'static [TypeId] {
+        &[]
+    }
+
+    /// Returns names matching [`Self::expected_resources`] for error messages.
+    #[must_use]
+    fn expected_resource_names() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the `TypeId`s of expected components that must have been added
+    /// before this component, rather than in the same operation.
+    #[must_use]
+    fn expected_prior() -> &'static [TypeId] {
+        &[]
+    }
+
+    /// Returns names matching [`Self::expected_prior`] for error messages.
+    #[must_use]
+    fn expected_prior_names() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns expectations that a component is present on both this entity and
+    /// the target of one of its relationships.
+    #[must_use]
+    fn expected_on_both_ends() -> &'static [RelatedExpectation] {
+        &[]
+    }
+
+    /// Returns expectations that a component is present on the target of one of
+    /// this entity's relationships.
+    #[must_use]
+    fn expected_on_related() -> &'static [RelatedExpectation] {
+        &[]
+    }
+
+    /// Returns expectations on the number of entities pointing at this one
+    /// through a relationship. These are checked once per frame.
+    #[must_use]
+    fn expected_incoming() -> &'static [IncomingExpectation] {
+        &[]
+    }
+
+    /// Returns the `TypeId`s of components every direct child of this entity
+    /// must have. These are checked once per frame.
+    #[must_use]
+    fn expected_on_all_children() -> &'static [TypeId] {
+        &[]
+    }
+
+    /// Returns names matching [`Self::expected_on_all_children`] for error messages.
+    #[must_use]
+    fn expected_on_all_children_names() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns expectations on how many direct children of this entity have a
+    /// component. These are checked once per frame.
+    #[must_use]
+    fn expected_child_counts() -> &'static [ChildCountExpectation] {
+        &[]
+    }
+
+    /// Returns the `TypeId`s of components this entity's parent must have.
+    /// An entity without a parent fails these expectations.
+    #[must_use]
+    fn expected_on_parent() -> &'static [TypeId] {
+        &[]
+    }
+
+    /// Returns names matching [`Self::expected_on_parent`] for error messages.
+    #[must_use]
+    fn expected_on_parent_names() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns expectations that components on this entity were changed from
+    /// their default values.
+    #[must_use]
+    fn expected_non_default() -> &'static [NonDefaultExpectation] {
+        &[]
+    }
+
+    /// Returns expectations that components on this entity satisfy a predicate.
+    #[must_use]
+    fn expected_predicates() -> &'static [PredicateExpectation] {
+        &[]
+    }
+
+    /// Returns expectations that apply only while this enum component is a
+    /// particular variant.
+    #[must_use]
+    fn expected_by_variant() -> &'static [VariantExpectation] {
+        &[]
+    }
+
+    /// Returns the declared stability of expected components, for those
+    /// annotated with `stability = "..."`.
+    #[must_use]
+    fn expected_stability() -> &'static [(&'static str, Stability)] {
+        &[]
+    }
+
+    /// Returns the tags of expected components, for those annotated with
+    /// `tags = [...]`.
+    #[must_use]
+    fn expected_tags() -> &'static [(&'static str, &'static [&'static str])] {
+        &[]
+    }
+
+    /// Returns the failure modes that override the global [`FailureMode`] for
+    /// individual expected components.
+    #[must_use]
+    fn expected_modes() -> &'static [(&'static str, FailureMode)] {
+        &[]
+    }
+
+    /// Returns custom messages appended to the failure messages of individual
+    /// expected components, as `(component name, template)` pairs.
+    ///
+    /// `{expecting}`, `{missing}` and `{entity}` in a template are replaced with
+    /// the expecting component, the missing component and the entity.
+    #[must_use]
+    fn expected_messages() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Returns true if components in this type's `#[require(...)]` closure satisfy
+    /// its expectations even when they are not on the entity.
+    #[must_use]
+    fn include_required_components() -> bool {
+        false
+    }
+
+    /// Installs add-order tracking for every type in [`Self::expected_prior`].
+    ///
+    /// Implementations should call [`track_add_order`] once per prior type.
+    fn register_add_order_tracking(_world: &mut World) {}
 }
 
 /// Registration entry for a component with expectations.
 ///
 /// Created by the `#[derive(ExpectComponents)]` macro and collected via `inventory`.
 /// You should not need to use this directly.
+#[derive(Clone, Copy)]
 pub struct ExpectRegistration {
+    type_id: TypeId,
+    type_name: fn() -> &'static str,
     register_hooks: fn(&mut World),
+    collect_violations: fn(&World) -> Vec<ExpectationViolation>,
+    collect_deferred_violations: fn(&World) -> Vec<ExpectationViolation>,
+    entities: fn(&World) -> Vec<Entity>,
+    check_entity: fn(&World, Entity) -> Vec<ExpectationViolation>,
+    check_bundle: fn(&[TypeId]) -> Vec<ExpectationViolation>,
+    find_inconsistency: fn() -> Option<String>,
+    expected_components: fn() -> &'static [TypeId],
+    expected_component_names: fn() -> &'static [&'static str],
+    describe_expectations: fn() -> Vec<String>,
+    expected_stability: fn() -> &'static [(&'static str, Stability)],
+    expected_tags: fn() -> &'static [(&'static str, &'static [&'static str])],
+    expected_modes: fn() -> &'static [(&'static str, FailureMode)],
+    hooks_only: bool,
 }
 
 impl ExpectRegistration {
-    /// Creates a new registration with a hook registration function.
+    /// Creates a registration for `T`.
+    ///
+    /// Called by the derive macro. Use with `inventory::submit!` when implementing
+    /// [`ExpectComponents`] by hand.
+    #[must_use]
+    pub const fn of<T: ExpectComponents>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>,
+            register_hooks: register_hooks_for::<T>,
+            collect_violations: scheduled::collect_violations_for::<T>,
+            collect_deferred_violations: scheduled::collect_deferred_violations_for::<T>,
+            entities: scheduled::entities_with::<T>,
+            check_entity: check_entity_for::<T>,
+            check_bundle: validation::check_bundle_for::<T>,
+            find_inconsistency: find_inconsistency_in::<T>,
+            expected_components: T::expected_components,
+            expected_component_names: T::expected_component_names,
+            describe_expectations: introspection::describe_expectations_of::<T>,
+            expected_stability: T::expected_stability,
+            expected_tags: T::expected_tags,
+            expected_modes: T::expected_modes,
+            hooks_only: false,
+        }
+    }
+
+    /// Creates a registration with a hook registration function.
     ///
-    /// Called by the derive macro. You should not need to use this directly.
+    /// The registration knows nothing about its type beyond the hooks, so
+    /// scheduled validation, introspection and per-expectation settings don't
+    /// see it.
+    #[deprecated(note = "use `ExpectRegistration::of::<T>()`, which carries the type's metadata")]
     #[must_use]
     pub const fn new(register_hooks: fn(&mut World)) -> Self {
-        Self { register_hooks }
+        Self {
+            type_id: TypeId::of::<Self>(),
+            type_name: || "",
+            register_hooks,
+            collect_violations: |_| Vec::new(),
+            collect_deferred_violations: |_| Vec::new(),
+            entities: |_| Vec::new(),
+            check_entity: |_, _| Vec::new(),
+            check_bundle: |_| Vec::new(),
+            find_inconsistency: || None,
+            expected_components: || &[],
+            expected_component_names: || &[],
+            describe_expectations: Vec::new,
+            expected_stability: || &[],
+            expected_tags: || &[],
+            expected_modes: || &[],
+            hooks_only: true,
+        }
+    }
+
+    /// Returns the `TypeId` of the expecting component.
+    #[must_use]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the type name of the expecting component.
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        (self.type_name)()
+    }
+
+    /// Returns the `TypeId`s of the components this type expects.
+    #[must_use]
+    pub fn expected_components(&self) -> &'static [TypeId] {
+        (self.expected_components)()
+    }
+
+    /// Returns the names of the components this type expects.
+    #[must_use]
+    pub fn expected_component_names(&self) -> &'static [&'static str] {
+        (self.expected_component_names)()
+    }
+
+    /// Returns the declared stability of the expected components annotated with one.
+    #[must_use]
+    pub fn expected_stability(&self) -> &'static [(&'static str, Stability)] {
+        (self.expected_stability)()
+    }
+
+    /// Returns the tags of the expected components annotated with any.
+    #[must_use]
+    pub fn expected_tags(&self) -> &'static [(&'static str, &'static [&'static str])] {
+        (self.expected_tags)()
+    }
+
+    /// Returns the failure modes overriding the global one for individual
+    /// expected components.
+    #[must_use]
+    pub fn expected_modes(&self) -> &'static [(&'static str, FailureMode)] {
+        (self.expected_modes)()
+    }
+
+    /// Returns one human-readable line per expectation declared by this type.
+    #[must_use]
+    pub fn describe_expectations(&self) -> Vec<String> {
+        (self.describe_expectations)()
     }
 
     /// Registers the component hooks with the world.
+    ///
+    /// Registering the same type again is a no-op, so explicit registration can
+    /// be combined with the plugin's `inventory` registration.
     pub fn register(&self, world: &mut World) {
-        (self.register_hooks)(world);
+        // Hooks-only registrations share a placeholder type, so are never deduplicated
+        let first = self.hooks_only
+            || world
+                .get_resource_or_init::<RegisteredExpectations>()
+                .0
+                .insert(self.type_id);
+
+        if first {
+            (self.register_hooks)(world);
+        }
+    }
+
+    /// Returns a description of the problem if the type's expectation metadata is
+    /// internally inconsistent, such as `TypeId`s and names of different lengths.
+    #[must_use]
+    pub fn find_inconsistency(&self) -> Option<String> {
+        (self.find_inconsistency)()
+    }
+
+    /// Returns every violation of this type's expectations across the world.
+    pub(crate) fn collect_violations(&self, world: &World) -> Vec<ExpectationViolation> {
+        (self.collect_violations)(world)
     }
+
+    /// Returns every violation of this type's per-frame expectations across the world.
+    pub(crate) fn collect_deferred_violations(&self, world: &World) -> Vec<ExpectationViolation> {
+        (self.collect_deferred_violations)(world)
+    }
+
+    /// Returns every entity with the expecting component.
+    pub(crate) fn entities(&self, world: &World) -> Vec<Entity> {
+        (self.entities)(world)
+    }
+
+    /// Returns the violations of this type's expectations on `entity`.
+    ///
+    /// Returns nothing if the entity does not have the expecting component.
+    #[must_use]
+    pub fn check_entity(&self, world: &World, entity: Entity) -> Vec<ExpectationViolation> {
+        (self.check_entity)(world, entity)
+    }
+
+    /// Returns the violations of this type's presence expectations by a bundle
+    /// of `components`, or nothing if the bundle does not contain this type.
+    pub(crate) fn check_bundle(&self, components: &[TypeId]) -> Vec<ExpectationViolation> {
+        (self.check_bundle)(components)
+    }
+}
+
+/// Expecting types whose hooks have been registered with the world.
+#[derive(Resource, Default)]
+pub(crate) struct RegisteredExpectations(HashSet<TypeId>);
+
+/// Registers the hooks of each listed expecting type with a world.
+///
+/// The plugin discovers expecting types through `inventory`, which is
+/// unreliable on some platforms. Invoke this once per module with the module's
+/// expecting types to register them explicitly. Types already registered, by
+/// an earlier call or by the plugin, are skipped.
+///
+/// Only insert-time validation is installed this way. Registry-wide features,
+/// such as scheduled validation and introspection, still rely on `inventory`.
+///
+/// ```rust,ignore
+/// register_module_expectations!(app.world_mut(), PhysicsBody, Vehicle, Wheel);
+/// ```
+#[macro_export]
+macro_rules! register_module_expectations {
+    ($world:expr $(, $expecting:ty)* $(,)?) => {{
+        let world: &mut $crate::__World = $world;
+        $($crate::ExpectRegistration::of::<$expecting>().register(world);)*
+    }};
+}
+
+/// Submits `T`'s registration for the plugin to find without `inventory`.
+/// Called by the `__register_<type>` functions the derive macro generates.
+#[doc(hidden)]
+pub fn __submit_registration<T: ExpectComponents>() {
+    registry::submit(ExpectRegistration::of::<T>());
+}
+
+/// Returns `named` followed by the names of the `#[expect_fn]` components in
+/// `computed`, as registered in the worlds seen so far. Used by the derive macro.
+#[doc(hidden)]
+#[must_use]
+pub fn __with_computed_names(
+    expecting: TypeId,
+    named: &[&'static str],
+    computed: &[TypeId],
+) -> &'static [&'static str] {
+    computed::with_computed_names(expecting, named, computed)
 }
 
 /// Registers component hooks for type T. Used by the derive macro.
 #[doc(hidden)]
 pub fn register_hooks_for<T: ExpectComponents>(world: &mut World) {
-    world
-        .register_component_hooks::<T>()
-        .on_add(validate_expected::<T>);
+    computed::learn_names(world, T::computed_components());
+    let on_insert = world.contains_resource::<config::ValidateOnInsert>();
+    let hooks = world.register_component_hooks::<T>();
+    if on_insert {
+        hooks.on_insert(validate_expected::<T>);
+    } else {
+        hooks.on_add(validate_expected::<T>);
+    }
+    T::register_add_order_tracking(world);
+}
+
+/// Checks `entity` against `T`'s expectations if it has `T`.
+fn check_entity_for<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    if world.get::<T>(entity).is_none() {
+        return Vec::new();
+    }
+
+    check_expectations::<T>(world, entity)
+}
+
+/// Checks that `T`'s `TypeId` and name lists line up, since they are zipped
+/// during validation and a mismatch would silently skip checks.
+fn find_inconsistency_in<T: ExpectComponents>() -> Option<String> {
+    let lengths = [
+        (
+            "expected_components",
+            T::expected_components().len(),
+            T::expected_component_names().len(),
+        ),
+        (
+            "expected_unless_present",
+            T::expected_unless_present().len(),
+            T::expected_unless_present_names().len(),
+        ),
+        (
+            "expected_if",
+            T::expected_if().len(),
+            T::expected_if_names().len(),
+        ),
+        (
+            "expected_any",
+            T::expected_any().len(),
+            T::expected_any_names().len(),
+        ),
+        (
+            "expected_one_of",
+            T::expected_one_of().len(),
+            T::expected_one_of_names().len(),
+        ),
+        (
+            "forbidden_components",
+            T::forbidden_components().len(),
+            T::forbidden_component_names().len(),
+        ),
+        (
+            "expected_resources",
+            T::expected_resources().len(),
+            T::expected_resource_names().len(),
+        ),
+        (
+            "expected_prior",
+            T::expected_prior().len(),
+            T::expected_prior_names().len(),
+        ),
+        (
+            "expected_on_parent",
+            T::expected_on_parent().len(),
+            T::expected_on_parent_names().len(),
+        ),
+        (
+            "expected_on_all_children",
+            T::expected_on_all_children().len(),
+            T::expected_on_all_children_names().len(),
+        ),
+    ];
+
+    lengths
+        .iter()
+        .find(|(_, ids, names)| ids != names)
+        .map(|(method, ids, names)| {
+            format!("{method} returned {ids} TypeIds but its names method returned {names} names")
+        })
 }
 
 inventory::collect!(ExpectRegistration);
@@ -193,33 +828,568 @@ inventory::collect!(ExpectRegistration);
 /// my_crate::RoadNode expects bevy::transform::components::Transform
 /// but it was not found on entity 42v3
 /// ```
+///
+/// In debug builds, `build` also panics if a registered type's expectation
+/// metadata is inconsistent, for example a hand-written impl returning more
+/// `TypeId`s than names. Expectations that form a cycle are logged as a
+/// warning, or panic with [`Self::deny_expectation_cycles`].
+#[derive(Default, Clone, Copy, Debug)]
 pub struct ExpectedComponentsPlugin;
 
+#[allow(clippy::unused_self)] // Builder entry points taking the unit plugin by value
+impl ExpectedComponentsPlugin {
+    /// See [`ExpectedComponentsConfig::development`].
+    #[must_use]
+    pub fn development() -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::development()
+    }
+
+    /// See [`ExpectedComponentsConfig::ci`].
+    #[must_use]
+    pub fn ci() -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::ci()
+    }
+
+    /// See [`ExpectedComponentsConfig::deferred`].
+    #[must_use]
+    pub fn deferred() -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::deferred()
+    }
+
+    /// See [`ExpectedComponentsConfig::production`].
+    #[must_use]
+    pub fn production() -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::production()
+    }
+
+    /// See [`ExpectedComponentsConfig::with_failure_mode`].
+    #[must_use]
+    pub fn with_failure_mode(self, mode: FailureMode) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().with_failure_mode(mode)
+    }
+
+    /// See [`ExpectedComponentsConfig::with_registry`].
+    #[must_use]
+    pub fn with_registry(self, registry: ExpectationRegistry) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().with_registry(registry)
+    }
+
+    /// See [`ExpectedComponentsConfig::validate_after_flush`].
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn validate_after_flush(self, schedule: impl ScheduleLabel) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().validate_after_flush(schedule)
+    }
+
+    /// See [`ExpectedComponentsConfig::validate_changed_only`].
+    #[must_use]
+    pub fn validate_changed_only(self) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().validate_changed_only()
+    }
+
+    /// See [`ExpectedComponentsConfig::skip_bevy_internal`].
+    #[must_use]
+    pub fn skip_bevy_internal(self) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().skip_bevy_internal()
+    }
+
+    /// See [`ExpectedComponentsConfig::on_insert`].
+    #[must_use]
+    pub fn on_insert(self) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().on_insert()
+    }
+
+    /// See [`ExpectedComponentsConfig::deny_expectation_cycles`].
+    #[must_use]
+    pub fn deny_expectation_cycles(self) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().deny_expectation_cycles()
+    }
+
+    /// See [`ExpectedComponentsConfig::with_handler`].
+    #[must_use]
+    pub fn with_handler(
+        self,
+        handler: impl Fn(&ExpectationViolation) + Send + Sync + 'static,
+    ) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().with_handler(handler)
+    }
+
+    /// See [`ExpectedComponentsConfig::register`].
+    #[must_use]
+    pub fn register<T: ExpectComponents>(self) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().register::<T>()
+    }
+
+    /// See [`ExpectedComponentsConfig::validate_if`].
+    #[must_use]
+    pub fn validate_if<M>(self, condition: impl SystemCondition<M>) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().validate_if(condition)
+    }
+
+    /// See [`ExpectedComponentsConfig::run_in_state`].
+    #[cfg(feature = "state")]
+    #[must_use]
+    pub fn run_in_state<S: bevy_state::state::States>(self, state: S) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().run_in_state(state)
+    }
+
+    /// See [`ExpectedComponentsConfig::batch_scene_validation`].
+    #[cfg(feature = "scene")]
+    #[must_use]
+    pub fn batch_scene_validation(self) -> ExpectedComponentsConfig {
+        ExpectedComponentsConfig::default().batch_scene_validation()
+    }
+}
+
 impl Plugin for ExpectedComponentsPlugin {
     fn build(&self, app: &mut App) {
-        for registration in inventory::iter::<ExpectRegistration> {
+        ExpectedComponentsConfig::default().build(app);
+    }
+}
+
+/// [`ExpectedComponentsPlugin`] with options applied.
+///
+/// Built by the plugin's builder methods, such as
+/// `ExpectedComponentsPlugin.validate_after_flush(Update)`, and added
+/// to the app in its place. The default config behaves exactly like the plain
+/// plugin.
+#[derive(Default)]
+#[allow(clippy::struct_excessive_bools)] // Independent builder options, not a state machine
+pub struct ExpectedComponentsConfig {
+    schedule: Option<InternedScheduleLabel>,
+    changed_only: bool,
+    skip_bevy_internal: bool,
+    on_insert: bool,
+    deny_cycles: bool,
+    #[cfg(feature = "scene")]
+    batch_scenes: bool,
+    failure_mode: Option<FailureMode>,
+    handler: Mutex<Option<ViolationHandler>>,
+    registry: Option<ExpectationRegistry>,
+    manual: Vec<ExpectRegistration>,
+    condition: Mutex<Option<BoxedCondition>>,
+    preset: Option<presets::Preset>,
+}
+
+impl ExpectedComponentsConfig {
+    /// Preset for local development: every check runs in hooks and the first
+    /// failure panics at the insert that caused it.
+    ///
+    /// Each failure is preceded by a dump of the entity's components and
+    /// [`Name`](bevy_ecs::name::Name), from [`VerboseFailures`], and by a
+    /// backtrace, from [`FailureBacktraces`].
+    #[must_use]
+    pub fn development() -> Self {
+        Self {
+            preset: Some(presets::Preset::Development),
+            ..Self::default()
+        }
+        .with_failure_mode(FailureMode::Panic)
+    }
+
+    /// Preset for headless CI runs: every failure is collected into the
+    /// [`ValidationReport`] instead of stopping at the first.
+    ///
+    /// The report is written as JSON to `expectation_report.json` through
+    /// [`JsonReport`], and an [`ErrorBudget`] of zero fails the run at the end
+    /// of any frame that collected a failure. Insert either resource before
+    /// adding the plugin to change the path or the budget.
+    #[must_use]
+    pub fn ci() -> Self {
+        Self {
+            preset: Some(presets::Preset::Ci),
+            ..Self::default()
+        }
+        .with_failure_mode(FailureMode::Collect)
+    }
+
+    /// Validates at the end of each frame instead of in hooks, so an entity can
+    /// be assembled across several commands within a frame.
+    ///
+    /// Validation runs once per frame in [`Last`] over entities whose expecting
+    /// component was added or changed that frame. Failures use the configured
+    /// [`FailureMode`].
+    #[must_use]
+    pub fn deferred() -> Self {
+        Self::default()
+            .validate_after_flush(Last)
+            .validate_changed_only()
+    }
+
+    /// Preset for shipped builds: each missing component is warned about once,
+    /// at most ten failures are logged per frame, through
+    /// [`ViolationRateLimit`], and [`ValidationStats`] records what validation
+    /// costs.
+    ///
+    /// With the `diagnostics` feature, violations per frame are also published
+    /// as `VIOLATIONS_DIAGNOSTIC`. Insert a resource before adding the plugin
+    /// to tune it.
+    #[must_use]
+    pub fn production() -> Self {
+        Self {
+            preset: Some(presets::Preset::Production),
+            ..Self::default()
+        }
+        .with_failure_mode(FailureMode::WarnOnce)
+    }
+
+    /// Inserts `mode` as the [`FailureMode`] resource when the plugin is built.
+    #[must_use]
+    pub fn with_failure_mode(mut self, mode: FailureMode) -> Self {
+        self.failure_mode = Some(mode);
+        self
+    }
+
+    /// Passes every violation to `handler` instead of reporting it through the
+    /// [`FailureMode`], for example to forward it to a crash reporter.
+    ///
+    /// The handler is stored in the [`ViolationHandler`] resource, and may run
+    /// from hooks on any thread that applies commands, so it must be `Send`
+    /// and `Sync`. It may panic to stop the app.
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(ExpectedComponentsPlugin.with_handler(|violation| {
+    ///     sentry::capture_message(&violation.message, sentry::Level::Error);
+    /// }));
+    /// ```
+    #[must_use]
+    pub fn with_handler(
+        self,
+        handler: impl Fn(&ExpectationViolation) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            handler: Mutex::new(Some(ViolationHandler::new(handler))),
+            ..self
+        }
+    }
+
+    /// Validates only the types in `registry`, instead of every type collected
+    /// by `inventory`.
+    ///
+    /// Covers the hooks installed when the plugin is built and the per-frame
+    /// and scheduled passes. Free functions that take no plugin configuration,
+    /// such as [`registry_snapshot`], still read the global registry.
+    #[must_use]
+    pub fn with_registry(mut self, registry: ExpectationRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Validates `T` even if `inventory` never collected it.
+    ///
+    /// `inventory` does not work in every linking setup, such as dynamic
+    /// libraries, some WASM targets and hot-reloading builds, and types it misses
+    /// are silently never validated. Registering them here installs their hooks
+    /// alongside the collected types. A type found both ways is only registered once.
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(
+    ///     ExpectedComponentsPlugin
+    ///         .register::<PhysicsBody>()
+    ///         .register::<Vehicle>(),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn register<T: ExpectComponents>(mut self) -> Self {
+        self.manual.push(ExpectRegistration::of::<T>());
+        self
+    }
+
+    /// Validates in a system in `schedule` instead of in `on_add` hooks.
+    ///
+    /// The system runs in [`ValidationSystems`] directly after an [`ApplyDeferred`],
+    /// so it sees every command queued by systems ordered before that set. This
+    /// lets an entity be assembled across several commands without failing
+    /// halfway through.
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(ExpectedComponentsPlugin.validate_after_flush(Update));
+    /// app.add_systems(Update, spawn_enemies.before(ValidationSystems));
+    /// ```
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)] // Matches Bevy's `impl ScheduleLabel` convention
+    pub fn validate_after_flush(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = Some(schedule.intern());
+        self
+    }
+
+    /// Only runs the scheduled pass when `condition` holds. Requires
+    /// [`Self::validate_after_flush`].
+    ///
+    /// Any Bevy run condition works, such as a state check, a resource flag or
+    /// a timer:
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(
+    ///     ExpectedComponentsPlugin
+    ///         .validate_after_flush(Last)
+    ///         .validate_if(resource_exists::<DevMode>),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn validate_if<M>(self, condition: impl SystemCondition<M>) -> Self {
+        Self {
+            condition: Mutex::new(Some(Box::new(IntoSystem::into_system(condition)))),
+            ..self
+        }
+    }
+
+    /// Only validates while the app is in `state`, such as skipping checks
+    /// during loading screens.
+    ///
+    /// Hooks cannot be scoped to a state, so this validates in a per-frame pass
+    /// guarded by `run_if(in_state(state))`, in [`Last`] unless
+    /// [`Self::validate_after_flush`] picked another schedule. Entities spawned
+    /// outside the state are validated by the first pass after entering it.
+    /// Requires the `state` feature and Bevy's `StatesPlugin`.
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(ExpectedComponentsPlugin.run_in_state(AppState::InGame));
+    /// ```
+    #[cfg(feature = "state")]
+    #[must_use]
+    pub fn run_in_state<S: bevy_state::state::States>(self, state: S) -> Self {
+        let plugin = if self.schedule.is_some() {
+            self
+        } else {
+            self.validate_after_flush(Last)
+        };
+        plugin.validate_if(bevy_state::condition::in_state(state))
+    }
+
+    /// Only validates entities whose expecting component was added or changed
+    /// since the previous pass. Requires [`Self::validate_after_flush`].
+    ///
+    /// This keeps continuous validation cheap in steady state. Expected
+    /// components removed later are not noticed until the expecting component
+    /// itself changes.
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(
+    ///     ExpectedComponentsPlugin
+    ///         .validate_after_flush(Update)
+    ///         .validate_changed_only(),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn validate_changed_only(mut self) -> Self {
+        self.changed_only = true;
+        self
+    }
+
+    /// Skips validation for expecting types defined in Bevy's own crates, such
+    /// as `bevy::` or `bevy_transform::`. Third-party `bevy_*` crates are not
+    /// skipped.
+    ///
+    /// Expectations that merely reference Bevy components, such as
+    /// `#[expects(Transform)]` on your own type, are still validated.
+    #[must_use]
+    pub fn skip_bevy_internal(mut self) -> Self {
+        self.skip_bevy_internal = true;
+        self
+    }
+
+    /// Validates in `on_insert` hooks instead of `on_add` hooks.
+    ///
+    /// `on_add` only fires when an entity gains the expecting component, so
+    /// inserting it again over an existing value is not validated. `on_insert`
+    /// fires on every insert, including replacements, at the cost of
+    /// re-checking entities that are updated by insertion each frame. Only
+    /// types registered by this plugin use it; types registered earlier keep
+    /// their `on_add` hooks.
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(ExpectedComponentsPlugin.on_insert());
+    /// ```
+    #[must_use]
+    pub fn on_insert(mut self) -> Self {
+        self.on_insert = true;
+        self
+    }
+
+    /// Validates scenes spawned by Bevy's scene spawner as a batch, instead of
+    /// entity by entity as they are written into the world.
+    ///
+    /// Insertion-time validation is paused while the scene spawner runs, so a
+    /// single invalid entity no longer interrupts a load. Each scene instance
+    /// is validated once Bevy reports it ready, and its violations are recorded
+    /// in [`SceneValidationReport`] by entity rather than reported through the
+    /// [`FailureMode`]. Requires the `scene` feature.
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(ExpectedComponentsPlugin.batch_scene_validation());
+    /// ```
+    #[cfg(feature = "scene")]
+    #[must_use]
+    pub fn batch_scene_validation(mut self) -> Self {
+        self.batch_scenes = true;
+        self
+    }
+
+    /// Panics when the plugin is built if expectations form a cycle, instead
+    /// of logging a warning.
+    ///
+    /// If `A` expects `B` and `B` expects `A`, neither can be inserted on its
+    /// own, which is usually a design mistake.
+    #[must_use]
+    pub fn deny_expectation_cycles(mut self) -> Self {
+        self.deny_cycles = true;
+        self
+    }
+
+    /// Installs hooks for every registration in `registry` and inserts the
+    /// resources indexing them.
+    fn install_registrations(&self, app: &mut App, registry: &ExpectationRegistry) {
+        let mut expected_by = removal::ExpectedBy::default();
+        let mut lookup = ExpectationLookup::default();
+        let mut installed = lookup::InstalledExpectations::default();
+        for registration in registry.iter() {
+            #[cfg(debug_assertions)]
+            if let Some(problem) = registration.find_inconsistency() {
+                panic!(
+                    "{} has inconsistent expectation metadata: {problem}",
+                    registration.type_name()
+                );
+            }
+
+            if self.skip_bevy_internal && config::is_bevy_internal(registration.type_name()) {
+                continue;
+            }
+
             registration.register(app.world_mut());
+            expected_by.add(registration);
+            lookup.add(registration);
+            installed.add(registration);
+        }
+        app.insert_resource(expected_by);
+        app.insert_resource(lookup);
+        app.insert_resource(installed);
+    }
+
+    /// Adds the validation pass configured by [`Self::validate_after_flush`] to
+    /// `schedule`.
+    fn add_scheduled_pass(&self, app: &mut App, schedule: InternedScheduleLabel) {
+        app.insert_resource(scheduled::ScheduledValidation {
+            changed_only: self.changed_only,
+        });
+        app.init_resource::<scheduled::ValidationCursor>();
+
+        let mut pass = (ApplyDeferred, scheduled::validate_scheduled)
+            .chain()
+            .in_set(ValidationSystems);
+        if let Some(condition) = self
+            .condition
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            pass.run_if_dyn(condition);
         }
+        app.add_systems(schedule, pass);
     }
 }
 
-/// Validation hook called when a component with expectations is inserted.
-#[allow(clippy::needless_pass_by_value)] // Bevy hook signature requires owned DeferredWorld
-fn validate_expected<T: ExpectComponents>(world: DeferredWorld, ctx: HookContext) {
-    let expected = T::expected_components();
-    let names = T::expected_component_names();
-    let entity = ctx.entity;
-
-    for (type_id, name) in expected.iter().zip(names.iter()) {
-        let component_id = world.components().get_id(*type_id);
-        let has_component = component_id.is_some_and(|id| world.entity(entity).contains_id(id));
-
-        assert!(
-            has_component,
-            "{} expects {} but it was not found on entity {:?}",
-            std::any::type_name::<T>(),
-            name,
-            entity
+impl Plugin for ExpectedComponentsConfig {
+    #[cfg(feature = "validate")]
+    fn build(&self, app: &mut App) {
+        installed::mark_plugin_built();
+
+        let mut registry = self
+            .registry
+            .clone()
+            .unwrap_or_else(ExpectationRegistry::from_inventory);
+        for registration in &self.manual {
+            registry.add(*registration);
+        }
+
+        if self.on_insert {
+            app.insert_resource(config::ValidateOnInsert);
+        }
+
+        self.install_registrations(app, &registry);
+
+        for cycle in introspection::expectation_cycles(registry.iter().filter(|registration| {
+            !(self.skip_bevy_internal && config::is_bevy_internal(registration.type_name()))
+        })) {
+            let message = format!("expectations form a cycle: {}", cycle.join(" -> "));
+            assert!(!self.deny_cycles, "{message}");
+            log::warn!("{message}");
+        }
+        removal::watch_expected(app.world_mut());
+
+        if self.skip_bevy_internal {
+            app.insert_resource(config::SkipBevyInternal);
+        }
+
+        if self.registry.is_some() || !self.manual.is_empty() {
+            app.insert_resource(registry);
+        }
+
+        if let Some(mode) = &self.failure_mode {
+            app.insert_resource(mode.clone());
+        }
+
+        if let Some(preset) = self.preset {
+            preset.install(app);
+        }
+
+        if let Some(handler) = self
+            .handler
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            app.insert_resource(handler);
+        }
+
+        if let Some(schedule) = self.schedule {
+            self.add_scheduled_pass(app, schedule);
+        }
+
+        #[cfg(feature = "scene")]
+        if self.batch_scenes {
+            scene::install_batch_validation(app);
+        }
+
+        app.init_resource::<file_sink::PendingFailures>();
+        app.init_resource::<config::WarnedPatterns>();
+        app.init_resource::<ValidationReport>();
+        app.init_resource::<ExpectationGroups>();
+        app.init_resource::<component_ids::ComponentIdCache>();
+        app.init_resource::<rate_limit::FrameLogCount>();
+        app.init_resource::<reported::DeferredReported>();
+        app.add_systems(
+            Last,
+            (
+                asset::validate_asset_expectations,
+                single::validate_single_expectations,
+                scheduled::validate_deferred,
+                removal::watch_expected,
+                file_sink::flush_pending_failures,
+                rate_limit::end_frame,
+                ci::write_json_report,
+                ci::enforce_error_budget,
+            )
+                .chain(),
         );
+
+        #[cfg(feature = "diagnostics")]
+        {
+            use bevy_diagnostic::{Diagnostic, RegisterDiagnostic};
+
+            app.init_resource::<diagnostics::FrameViolations>();
+            app.register_diagnostic(Diagnostic::new(VIOLATIONS_DIAGNOSTIC));
+            app.add_systems(
+                Last,
+                diagnostics::measure_violations
+                    .after(file_sink::flush_pending_failures)
+                    .after(ValidationSystems),
+            );
+        }
     }
+
+    /// Without `validate` the plugin installs nothing, so no validation code
+    /// is compiled in.
+    #[cfg(not(feature = "validate"))]
+    fn build(&self, _app: &mut App) {}
 }
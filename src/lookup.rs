@@ -0,0 +1,122 @@
+//! Looking up expectations by `TypeId` or type name, for tooling that does not
+//! have the concrete type in hand.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::World;
+
+use crate::{registry, ExpectRegistration};
+
+/// The components each validated type expects, keyed by `TypeId` and by type
+/// name.
+///
+/// Inserted by [`ExpectedComponentsPlugin`](crate::ExpectedComponentsPlugin)
+/// when it is built, covering the same types it installs hooks for. Editors
+/// and inspectors can read it to show what any component expects:
+///
+/// ```rust,ignore
+/// let lookup = world.resource::<ExpectationLookup>();
+/// if let Some(expected) = lookup.expected_components_for(type_id) {
+///     ui.label(expected.join(", "));
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct ExpectationLookup {
+    by_type_id: HashMap<TypeId, &'static [&'static str]>,
+    by_name: HashMap<&'static str, &'static [&'static str]>,
+}
+
+impl ExpectationLookup {
+    /// Indexes the components `registration` expects, skipping hooks-only
+    /// registrations, which carry no type of their own.
+    pub(crate) fn add(&mut self, registration: &ExpectRegistration) {
+        if registration.hooks_only {
+            return;
+        }
+        let expected = registration.expected_component_names();
+        self.by_type_id.insert(registration.type_id(), expected);
+        self.by_name.insert(registration.type_name(), expected);
+    }
+
+    /// Returns the names of the components the type with `type_id` expects, or
+    /// `None` if it is not registered.
+    #[must_use]
+    pub fn expected_components_for(&self, type_id: TypeId) -> Option<&'static [&'static str]> {
+        self.by_type_id.get(&type_id).copied()
+    }
+
+    /// Returns the names of the components the type named `type_name` expects,
+    /// or `None` if it is not registered.
+    ///
+    /// `type_name` is the full path given by [`std::any::type_name`], such as
+    /// `my_game::Vehicle`.
+    #[must_use]
+    pub fn expected_components_for_name(&self, type_name: &str) -> Option<&'static [&'static str]> {
+        self.by_name.get(type_name).copied()
+    }
+}
+
+/// Every registration the plugin installed hooks for, keyed by `TypeId`.
+///
+/// Inserted when the plugin is built, so types from an
+/// [`ExpectationRegistry`](crate::ExpectationRegistry) or registered on the
+/// plugin resolve like those collected by `inventory`. Violations name their
+/// expecting type, so registrations are also indexed by type name.
+#[derive(Resource, Default)]
+pub(crate) struct InstalledExpectations {
+    registrations: Vec<ExpectRegistration>,
+    by_type_id: HashMap<TypeId, usize>,
+    by_name: HashMap<&'static str, TypeId>,
+}
+
+impl InstalledExpectations {
+    /// Records `registration`, skipping deprecated hooks-only registrations,
+    /// which have no type to look up.
+    pub(crate) fn add(&mut self, registration: &ExpectRegistration) {
+        if registration.hooks_only || self.by_type_id.contains_key(&registration.type_id()) {
+            return;
+        }
+
+        self.by_type_id
+            .insert(registration.type_id(), self.registrations.len());
+        self.by_name
+            .insert(registration.type_name(), registration.type_id());
+        self.registrations.push(*registration);
+    }
+
+    /// Returns the registration of the type with `type_id`.
+    pub(crate) fn get(&self, type_id: TypeId) -> Option<&ExpectRegistration> {
+        self.by_type_id
+            .get(&type_id)
+            .map(|&index| &self.registrations[index])
+    }
+}
+
+/// Returns the registrations validated in `world`: those the plugin installed,
+/// or every collected type if the plugin was not added.
+pub(crate) fn installed<'w>(
+    world: &'w World,
+) -> Box<dyn Iterator<Item = &'w ExpectRegistration> + 'w> {
+    match world.get_resource::<InstalledExpectations>() {
+        Some(installed) => Box::new(installed.registrations.iter()),
+        None => Box::new(
+            registry::collected_registrations()
+                .map(|registration| -> &'w ExpectRegistration { registration }),
+        ),
+    }
+}
+
+/// Returns the registration of the expecting type named `type_name`, as
+/// [`installed`] would list it.
+pub(crate) fn installed_named<'w>(
+    world: &'w World,
+    type_name: &str,
+) -> Option<&'w ExpectRegistration> {
+    match world.get_resource::<InstalledExpectations>() {
+        Some(installed) => installed.get(*installed.by_name.get(type_name)?),
+        None => registry::collected_registrations()
+            .find(|registration| registration.type_name() == type_name),
+    }
+}
@@ -0,0 +1,174 @@
+//! Expectations on components known only by name at runtime, for scripting and
+//! modding workflows.
+
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::sync::{PoisonError, RwLock};
+
+use bevy_ecs::component::ComponentId;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::lifecycle::Add;
+use bevy_ecs::observer::{Observer, On};
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::{DeferredWorld, World};
+
+use crate::validation::report_all;
+use crate::{config, validation_enabled, ExpectationViolation, ViolationKind};
+
+/// Names of the components each expecting type expects, registered at runtime.
+#[derive(Resource, Default)]
+struct NamedExpectations {
+    expected: HashMap<TypeId, (&'static str, Vec<&'static str>)>,
+    observed: HashSet<TypeId>,
+    /// Every name registered so far, leaked once and shared by later
+    /// registrations of the same name.
+    names: HashSet<&'static str>,
+    /// Expected names resolved to their `ComponentId`. Checks only hold a
+    /// shared reference to the world, so entries are added behind a lock. Names
+    /// matching no registered component are not cached and are resolved again
+    /// on the next check.
+    resolved: RwLock<HashMap<&'static str, ComponentId>>,
+}
+
+impl NamedExpectations {
+    /// Returns the leaked copy of `name`, leaking it only the first time.
+    fn intern(&mut self, name: &str) -> &'static str {
+        if let Some(&interned) = self.names.get(name) {
+            return interned;
+        }
+        let interned: &'static str = Box::leak(name.into());
+        self.names.insert(interned);
+        interned
+    }
+
+    /// Resolves `name` to the `ComponentId` of the component registered under
+    /// it, or `None` if there is none yet.
+    fn resolve(&self, world: &World, name: &'static str) -> Option<ComponentId> {
+        if let Some(&id) = self
+            .resolved
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+        {
+            return Some(id);
+        }
+
+        let id = world
+            .components()
+            .iter_registered()
+            .find(|info| *info.name() == *name)?
+            .id();
+        self.resolved
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name, id);
+        Some(id)
+    }
+}
+
+/// Registers an expectation that the component with `TypeId` `expecting` is
+/// accompanied by the component named `expected_name`.
+///
+/// The expected component is resolved by name through `world.components()`
+/// each time `expecting` is added, so it can be a component registered at
+/// runtime, such as one defined by a script or mod, as long as it is
+/// registered by the time it is needed. A name that matches no registered
+/// component counts as missing. Names are full type paths, as given by Bevy's
+/// `debug` feature, or the name a dynamic component was registered under.
+///
+/// `expecting` must already be registered as a component. It does not need to
+/// derive [`ExpectComponents`](crate::ExpectComponents); an observer watches it
+/// instead of a hook. Violations are reported through the configured
+/// [`FailureMode`](crate::FailureMode).
+///
+/// ```rust,ignore
+/// register_named_expectation(world, TypeId::of::<ScriptedUnit>(), "mods::Faction");
+/// ```
+///
+/// # Panics
+///
+/// Panics if no component with `TypeId` `expecting` is registered.
+pub fn register_named_expectation(world: &mut World, expecting: TypeId, expected_name: &str) {
+    let id = world
+        .components()
+        .get_id(expecting)
+        .unwrap_or_else(|| panic!("{expecting:?} is not a registered component"));
+    let expecting_name = world
+        .components()
+        .get_info(id)
+        .map(|info| info.name().to_string())
+        .unwrap_or_default();
+
+    let mut named = world.get_resource_or_init::<NamedExpectations>();
+    let expecting_name = named.intern(&expecting_name);
+    let expected_name = named.intern(expected_name);
+    let expected = &mut named
+        .expected
+        .entry(expecting)
+        .or_insert_with(|| (expecting_name, Vec::new()))
+        .1;
+    if !expected.contains(&expected_name) {
+        expected.push(expected_name);
+    }
+
+    if named.observed.insert(expecting) {
+        world.spawn(Observer::new(check_named).with_component(id));
+    }
+}
+
+/// Reports every named expectation the added components fail.
+#[allow(clippy::needless_pass_by_value)] // Bevy observer params are passed by value
+fn check_named(add: On<Add>, mut world: DeferredWorld) {
+    if !validation_enabled(&world) {
+        return;
+    }
+
+    let entity = add.entity;
+    let violations: Vec<ExpectationViolation> = add
+        .trigger()
+        .components
+        .iter()
+        .filter_map(|id| world.components().get_info(*id)?.type_id())
+        .filter(|type_id| !config::is_suppressed(&world, *type_id))
+        .flat_map(|type_id| named_violations(&world, entity, type_id))
+        .collect();
+
+    report_all(&mut world, &violations);
+}
+
+/// Returns a violation for each component named as expected by `expecting`
+/// that `entity` lacks.
+fn named_violations(world: &World, entity: Entity, expecting: TypeId) -> Vec<ExpectationViolation> {
+    let Some(expectations) = world.get_resource::<NamedExpectations>() else {
+        return Vec::new();
+    };
+    let Some((expecting, names)) = expectations.expected.get(&expecting) else {
+        return Vec::new();
+    };
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+
+    names
+        .iter()
+        .filter(|&&name| {
+            !expectations
+                .resolve(world, name)
+                .is_some_and(|id| entity_ref.contains_id(id))
+        })
+        .map(|&name| {
+            let location = format!("on entity {entity:?}");
+            ExpectationViolation {
+                entity,
+                expecting,
+                missing: name,
+                message: format!("{expecting} expects {name} but it was not found {location}"),
+                kind: ViolationKind::Missing {
+                    location,
+                    note: None,
+                },
+                caller: None,
+            }
+        })
+        .collect()
+}
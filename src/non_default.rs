@@ -0,0 +1,109 @@
+//! Expectations that a component was configured rather than left at its default.
+
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::world::World;
+
+use crate::{ExpectComponents, ExpectationViolation, ViolationKind};
+
+/// An expectation that a component on the entity differs from its `Default` value.
+///
+/// Generated by `#[expect_non_default(Config)]`, which also expects the component
+/// to be present.
+pub struct NonDefaultExpectation {
+    /// Name of the component for error messages.
+    pub component: &'static str,
+    /// Returns true if the entity has the component and it equals its default.
+    pub is_default: fn(&World, Entity) -> bool,
+    /// Returns true if the entity's component was mutated in place since the
+    /// last frame, rather than just added.
+    pub was_mutated: fn(&World, Entity) -> bool,
+}
+
+/// Returns true if the entity's `C` equals `C::default()`.
+///
+/// Returns false if the entity has no `C`, since absence is reported separately.
+#[must_use]
+pub fn is_default_value<C: Component + Default + PartialEq>(world: &World, entity: Entity) -> bool {
+    world
+        .get::<C>(entity)
+        .is_some_and(|component| *component == C::default())
+}
+
+/// Returns true if the entity's `C` was changed since the last frame without
+/// being added, meaning it was mutated in place.
+#[must_use]
+pub fn was_mutated<C: Component>(world: &World, entity: Entity) -> bool {
+    world
+        .get_entity(entity)
+        .ok()
+        .and_then(|entity| entity.get_ref::<C>())
+        .is_some_and(|component| component.is_changed() && !component.is_added())
+}
+
+/// Returns every one of `T`'s non-default expectations that `entity` fails.
+pub(crate) fn check_non_default<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expecting = std::any::type_name::<T>();
+
+    T::expected_non_default()
+        .iter()
+        .filter(|expectation| (expectation.is_default)(world, entity))
+        .map(|expectation| {
+            let component = expectation.component;
+            ExpectationViolation {
+                entity,
+                expecting,
+                missing: component,
+                message: format!(
+                    "{expecting} expects {component} to differ from its default value on entity {entity:?}"
+                ),
+                kind: ViolationKind::Other,
+                caller: None,
+            }
+        })
+        .collect()
+}
+
+/// Returns every one of `T`'s non-default expectations that `entity` fails
+/// because the component was mutated back to its default after insertion.
+///
+/// Insertion is validated by the hook, so only in-place mutations are checked
+/// here, once per frame.
+pub(crate) fn check_mutated<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expecting = std::any::type_name::<T>();
+
+    T::expected_non_default()
+        .iter()
+        .filter(|expectation| {
+            (expectation.was_mutated)(world, entity) && (expectation.is_default)(world, entity)
+        })
+        .map(|expectation| {
+            let component = expectation.component;
+            ExpectationViolation {
+                entity,
+                expecting,
+                missing: component,
+                message: format!(
+                    "{expecting} expects {component} to differ from its default value, but it was changed back to its default on entity {entity:?}"
+                ),
+                kind: ViolationKind::Other,
+                caller: None,
+            }
+        })
+        .collect()
+}
+
+/// Returns true if any of `T`'s non-default components on `entity` were mutated
+/// in place since the last frame.
+pub(crate) fn any_mutated<T: ExpectComponents>(world: &World, entity: Entity) -> bool {
+    T::expected_non_default()
+        .iter()
+        .any(|expectation| (expectation.was_mutated)(world, entity))
+}
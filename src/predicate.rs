@@ -0,0 +1,87 @@
+//! Expectations on the value of an expected component, not just its presence.
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::world::World;
+
+use crate::{ExpectComponents, ExpectationViolation, ViolationKind};
+
+/// An expectation that a component on the entity satisfies a predicate.
+///
+/// Generated by `#[expect_where(Health, |health: &Health| health.0 > 0.0)]`,
+/// which also expects the component to be present.
+pub struct PredicateExpectation {
+    /// Name of the component for error messages.
+    pub component: &'static str,
+    /// Returns true if the entity has the component and its value fails the
+    /// predicate.
+    pub fails: fn(&World, Entity) -> bool,
+    /// Returns true if the entity's component was mutated in place since the
+    /// last frame, rather than just added.
+    pub was_mutated: fn(&World, Entity) -> bool,
+}
+
+/// Returns every one of `T`'s predicate expectations that `entity` fails.
+pub(crate) fn check_predicates<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expecting = std::any::type_name::<T>();
+
+    T::expected_predicates()
+        .iter()
+        .filter(|expectation| (expectation.fails)(world, entity))
+        .map(|expectation| {
+            let component = expectation.component;
+            ExpectationViolation {
+                entity,
+                expecting,
+                missing: component,
+                message: format!(
+                    "{expecting} expects {component} to satisfy its predicate but the value failed on entity {entity:?}"
+                ),
+                kind: ViolationKind::Other,
+                caller: None,
+            }
+        })
+        .collect()
+}
+
+/// Returns every one of `T`'s predicate expectations that `entity` fails
+/// because the component was mutated to a failing value after insertion.
+///
+/// Insertion is validated by the hook, so only in-place mutations are checked
+/// here, once per frame.
+pub(crate) fn check_mutated<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expecting = std::any::type_name::<T>();
+
+    T::expected_predicates()
+        .iter()
+        .filter(|expectation| {
+            (expectation.was_mutated)(world, entity) && (expectation.fails)(world, entity)
+        })
+        .map(|expectation| {
+            let component = expectation.component;
+            ExpectationViolation {
+                entity,
+                expecting,
+                missing: component,
+                message: format!(
+                    "{expecting} expects {component} to satisfy its predicate, but it was changed to a failing value on entity {entity:?}"
+                ),
+                kind: ViolationKind::Other,
+                caller: None,
+            }
+        })
+        .collect()
+}
+
+/// Returns true if any of `T`'s predicate components on `entity` were mutated
+/// in place since the last frame.
+pub(crate) fn any_mutated<T: ExpectComponents>(world: &World, entity: Entity) -> bool {
+    T::expected_predicates()
+        .iter()
+        .any(|expectation| (expectation.was_mutated)(world, entity))
+}
@@ -0,0 +1,51 @@
+//! Configuration resources bundled by the plugin presets.
+
+use bevy_app::App;
+use bevy_ecs::resource::Resource;
+
+use crate::{
+    ErrorBudget, FailureBacktraces, JsonReport, ValidationStats, VerboseFailures,
+    ViolationRateLimit,
+};
+
+/// Where [`ExpectedComponentsConfig::ci`](crate::ExpectedComponentsConfig::ci)
+/// writes its report.
+pub(crate) const CI_REPORT_PATH: &str = "expectation_report.json";
+
+/// Failures logged per frame by
+/// [`ExpectedComponentsConfig::production`](crate::ExpectedComponentsConfig::production).
+pub(crate) const PRODUCTION_RATE_LIMIT: u32 = 10;
+
+/// A bundle of knobs picked by one of the preset constructors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Preset {
+    Development,
+    Ci,
+    Production,
+}
+
+impl Preset {
+    /// Inserts the preset's resources, keeping any the app already has.
+    pub(crate) fn install(self, app: &mut App) {
+        match self {
+            Preset::Development => {
+                insert_unless_present(app, VerboseFailures);
+                insert_unless_present(app, FailureBacktraces);
+            }
+            Preset::Ci => {
+                insert_unless_present(app, JsonReport(CI_REPORT_PATH.into()));
+                insert_unless_present(app, ErrorBudget(0));
+            }
+            Preset::Production => {
+                insert_unless_present(app, ViolationRateLimit(PRODUCTION_RATE_LIMIT));
+                insert_unless_present(app, ValidationStats::default());
+            }
+        }
+    }
+}
+
+fn insert_unless_present<R: Resource>(app: &mut App, resource: R) {
+    if !app.world().contains_resource::<R>() {
+        app.insert_resource(resource);
+    }
+}
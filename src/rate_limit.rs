@@ -0,0 +1,63 @@
+//! Capping how many failures are logged each frame.
+
+use bevy_ecs::resource::Resource;
+use bevy_ecs::system::ResMut;
+use bevy_ecs::world::DeferredWorld;
+
+/// Logs at most this many failures per frame.
+///
+/// Applies to the failure modes that log: [`FailureMode::Warn`],
+/// [`FailureMode::Log`], [`FailureMode::WarnOnce`] and the first warning of
+/// [`FailureMode::WarnThenPanic`]. Failures over the limit are counted and
+/// summarized in one warning at the end of the frame. A pattern suppressed under
+/// `WarnOnce` is not marked as warned, so it is logged on a later frame.
+/// Panics, collected reports and handlers are never limited.
+///
+/// ```rust,ignore
+/// app.insert_resource(ViolationRateLimit(10));
+/// ```
+///
+/// [`FailureMode::Warn`]: crate::FailureMode::Warn
+/// [`FailureMode::Log`]: crate::FailureMode::Log
+/// [`FailureMode::WarnOnce`]: crate::FailureMode::WarnOnce
+/// [`FailureMode::WarnThenPanic`]: crate::FailureMode::WarnThenPanic
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViolationRateLimit(pub u32);
+
+/// Failures logged and suppressed so far this frame.
+#[derive(Resource, Default)]
+pub(crate) struct FrameLogCount {
+    logged: u32,
+    suppressed: u32,
+}
+
+/// Counts a failure about to be logged, returning false if this frame's limit
+/// is used up.
+pub(crate) fn allow(world: &mut DeferredWorld) -> bool {
+    let Some(ViolationRateLimit(limit)) = world.get_resource::<ViolationRateLimit>().copied()
+    else {
+        return true;
+    };
+    let Some(mut count) = world.get_resource_mut::<FrameLogCount>() else {
+        return true;
+    };
+
+    if count.logged < limit {
+        count.logged += 1;
+        true
+    } else {
+        count.suppressed = count.suppressed.saturating_add(1);
+        false
+    }
+}
+
+/// Summarizes this frame's suppressed failures and starts counting anew.
+pub(crate) fn end_frame(mut count: ResMut<FrameLogCount>) {
+    if count.suppressed > 0 {
+        log::warn!(
+            "{} more expectation failures were not logged this frame because of ViolationRateLimit",
+            count.suppressed
+        );
+    }
+    *count = FrameLogCount::default();
+}
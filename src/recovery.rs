@@ -0,0 +1,83 @@
+//! Constructing missing expected components instead of reporting them.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::{DeferredWorld, World};
+
+use crate::{has_component, ExpectComponents};
+
+type Recovery = Arc<dyn Fn(&mut World, Entity) + Send + Sync>;
+
+/// Registered recoveries, keyed by the `TypeId` of the component they construct.
+#[derive(Resource, Default)]
+struct Recoveries(HashMap<TypeId, Recovery>);
+
+/// Registers `recover` to construct `C` whenever an expected `C` is missing.
+///
+/// Instead of failing, validation queues a command that calls `recover` with
+/// full world access and inserts the result. This suits components that have no
+/// sensible `Default` or need other data from the world to construct, which
+/// `#[require]` cannot handle. Registering again for the same type replaces the
+/// previous recovery.
+///
+/// ```rust,ignore
+/// register_recovery::<Velocity>(app.world_mut(), |world, _entity| {
+///     Velocity::from_world(world)
+/// });
+/// ```
+pub fn register_recovery<C: Component>(
+    world: &mut World,
+    recover: impl Fn(&mut World, Entity) -> C + Send + Sync + 'static,
+) {
+    let recovery: Recovery = Arc::new(move |world: &mut World, entity: Entity| {
+        let component = recover(world, entity);
+        if let Ok(mut entity) = world.get_entity_mut(entity) {
+            entity.insert(component);
+        }
+    });
+
+    world
+        .get_resource_or_init::<Recoveries>()
+        .0
+        .insert(TypeId::of::<C>(), recovery);
+}
+
+/// Queues recoveries for every component `T` expects that is missing from
+/// `entity` and has one registered, returning the names of those components.
+pub(crate) fn recover_missing<T: ExpectComponents>(
+    world: &mut DeferredWorld,
+    entity: Entity,
+) -> Vec<&'static str> {
+    let Some(recoveries) = world.get_resource::<Recoveries>() else {
+        return Vec::new();
+    };
+
+    let mut recovered = Vec::new();
+    let mut queued = Vec::new();
+
+    for (type_id, name) in T::expected_components()
+        .iter()
+        .zip(T::expected_component_names())
+    {
+        if has_component(world, entity, *type_id) {
+            continue;
+        }
+
+        if let Some(recovery) = recoveries.0.get(type_id) {
+            queued.push(Arc::clone(recovery));
+            recovered.push(*name);
+        }
+    }
+
+    let mut commands = world.commands();
+    for recovery in queued {
+        commands.queue(move |world: &mut World| recovery(world, entity));
+    }
+
+    recovered
+}
@@ -0,0 +1,104 @@
+//! Standalone sets of registrations, as an alternative to the global `inventory`.
+
+use std::sync::{Mutex, PoisonError};
+
+use bevy_ecs::resource::Resource;
+
+use crate::{ExpectComponents, ExpectRegistration};
+
+/// Registrations submitted by the registrars `register_all_expectations!`
+/// calls, for platforms where `inventory` collects nothing.
+///
+/// Leaked so they can be handed out alongside `inventory`'s `'static` ones.
+/// Each type is submitted at most once, so this is bounded by the number of
+/// expecting types.
+static SUBMITTED: Mutex<Vec<&'static ExpectRegistration>> = Mutex::new(Vec::new());
+
+/// Adds `registration` to the process-wide registrations, unless its type was
+/// already collected by `inventory` or submitted before.
+pub(crate) fn submit(registration: ExpectRegistration) {
+    let mut submitted = SUBMITTED.lock().unwrap_or_else(PoisonError::into_inner);
+    let known = inventory::iter::<ExpectRegistration>
+        .into_iter()
+        .chain(submitted.iter().copied())
+        .any(|existing| !existing.hooks_only && existing.type_id() == registration.type_id());
+
+    if !known {
+        submitted.push(Box::leak(Box::new(registration)));
+    }
+}
+
+/// Returns every registration collected by `inventory`, followed by those
+/// submitted through `register_all_expectations!`.
+pub(crate) fn collected_registrations() -> impl Iterator<Item = &'static ExpectRegistration> {
+    let submitted = SUBMITTED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    inventory::iter::<ExpectRegistration>
+        .into_iter()
+        .chain(submitted)
+}
+
+/// A set of expecting types the plugin validates, built independently of the
+/// global `inventory` registry.
+///
+/// Hand one to [`ExpectedComponentsPlugin::with_registry`](crate::ExpectedComponentsPlugin::with_registry)
+/// to validate exactly these types, for example to give each test its own
+/// expectations without seeing every type linked into the binary. The global
+/// registry is one way to populate it, through [`Self::from_inventory`].
+///
+/// ```rust,ignore
+/// let registry = ExpectationRegistry::new()
+///     .with::<PhysicsBody>()
+///     .with::<Vehicle>();
+/// app.add_plugins(ExpectedComponentsPlugin.with_registry(registry));
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct ExpectationRegistry {
+    registrations: Vec<ExpectRegistration>,
+}
+
+impl ExpectationRegistry {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry holding every registration collected by `inventory`,
+    /// and those submitted through
+    /// [`register_all_expectations!`](crate::register_all_expectations).
+    #[must_use]
+    pub fn from_inventory() -> Self {
+        Self {
+            registrations: collected_registrations().copied().collect(),
+        }
+    }
+
+    /// Adds `T` to the registry.
+    #[must_use]
+    pub fn with<T: ExpectComponents>(mut self) -> Self {
+        self.add(ExpectRegistration::of::<T>());
+        self
+    }
+
+    /// Adds `registration`, ignoring it if its type is already present.
+    ///
+    /// Deprecated hooks-only registrations have no type to compare, so are always added.
+    pub fn add(&mut self, registration: ExpectRegistration) {
+        if registration.hooks_only
+            || !self
+                .registrations
+                .iter()
+                .any(|existing| existing.type_id() == registration.type_id())
+        {
+            self.registrations.push(registration);
+        }
+    }
+
+    /// Returns the registrations in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &ExpectRegistration> {
+        self.registrations.iter()
+    }
+}
@@ -0,0 +1,165 @@
+//! Expectations that follow relationships to other entities.
+
+use std::any::TypeId;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::relationship::{Relationship, RelationshipTarget};
+use bevy_ecs::world::World;
+
+use crate::{has_component, ExpectComponents, ExpectationViolation, ViolationKind};
+
+/// An expectation involving the entity targeted by a relationship.
+///
+/// Generated by relationship attributes such as `#[expect_both_ends(LinkedTo, Synced)]`
+/// and `#[expect_on_related(EquippedBy, Hand)]`.
+pub struct RelatedExpectation {
+    /// Type name of the relationship component.
+    pub relationship: &'static str,
+    /// Returns the relationship target of an entity, if it has the relationship.
+    pub target: fn(&World, Entity) -> Option<Entity>,
+    /// `TypeId` of the expected component.
+    pub expected: TypeId,
+    /// Name of the expected component for error messages.
+    pub expected_name: &'static str,
+}
+
+/// An expectation that at least `min` entities point at this one through a relationship.
+///
+/// Generated by `#[expect_incoming_relations(ConnectedTo, min = 2)]`. Incoming
+/// relationships are usually added after the target spawns, so these are checked
+/// once per frame rather than at insertion.
+pub struct IncomingExpectation {
+    /// Type name of the relationship component on the source entities.
+    pub relationship: &'static str,
+    /// Returns how many entities point at an entity through the relationship.
+    pub count: fn(&World, Entity) -> usize,
+    /// Minimum number of incoming relationships.
+    pub min: usize,
+}
+
+/// Returns how many entities point at `entity` through their `R` relationship.
+#[must_use]
+pub fn incoming_relationship_count<R: Relationship>(world: &World, entity: Entity) -> usize {
+    world
+        .get::<R::RelationshipTarget>(entity)
+        .map_or(0, RelationshipTarget::len)
+}
+
+/// Returns the target of the entity's `R` relationship, if it has one.
+#[must_use]
+pub fn relationship_target<R: Relationship>(world: &World, entity: Entity) -> Option<Entity> {
+    world.get::<R>(entity).map(Relationship::get)
+}
+
+/// Returns every one of `T`'s relationship expectations that `entity` fails.
+pub(crate) fn check_related<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let mut violations = Vec::new();
+    let expecting = std::any::type_name::<T>();
+
+    for expectation in T::expected_on_both_ends() {
+        let relationship = expectation.relationship;
+        let expected = expectation.expected_name;
+        let violation = |message| ExpectationViolation {
+            entity,
+            expecting,
+            missing: expected,
+            message,
+            kind: ViolationKind::Other,
+            caller: None,
+        };
+
+        let Some(target) = (expectation.target)(world, entity) else {
+            violations.push(violation(format!(
+                "{expecting} expects a {relationship} relationship but none was found on entity {entity:?}"
+            )));
+            continue;
+        };
+
+        if !has_component(world, entity, expectation.expected) {
+            violations.push(violation(format!(
+                "{expecting} expects {expected} on both ends of {relationship}, but it was not found on entity {entity:?}"
+            )));
+        }
+
+        if world.get_entity(target).is_err() {
+            violations.push(violation(format!(
+                "{expecting} expects {expected} on both ends of {relationship}, but target {target:?} of entity {entity:?} does not exist"
+            )));
+        } else if !has_component(world, target, expectation.expected) {
+            violations.push(violation(format!(
+                "{expecting} expects {expected} on both ends of {relationship}, but it was not found on target {target:?} of entity {entity:?}"
+            )));
+        }
+    }
+
+    violations.extend(check_on_related::<T>(world, entity));
+    violations
+}
+
+/// Returns every one of `T`'s expectations on its relationship targets that `entity` fails.
+fn check_on_related<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expecting = std::any::type_name::<T>();
+
+    T::expected_on_related()
+        .iter()
+        .filter_map(|expectation| {
+            let relationship = expectation.relationship;
+            let expected = expectation.expected_name;
+
+            let message = match (expectation.target)(world, entity) {
+                None => format!(
+                    "{expecting} expects {expected} on its {relationship} target, but entity {entity:?} has no {relationship}"
+                ),
+                Some(target) if world.get_entity(target).is_err() => format!(
+                    "{expecting} expects {expected} on its {relationship} target, but target {target:?} of entity {entity:?} does not exist"
+                ),
+                Some(target) if !has_component(world, target, expectation.expected) => format!(
+                    "{expecting} expects {expected} on its {relationship} target, but it was not found on target {target:?} of entity {entity:?}"
+                ),
+                Some(_) => return None,
+            };
+
+            Some(ExpectationViolation {
+                entity,
+                expecting,
+                missing: expected,
+                message,
+                kind: ViolationKind::Other,
+                caller: None,
+            })
+        })
+        .collect()
+}
+
+/// Returns every one of `T`'s incoming relationship expectations that `entity` fails.
+pub(crate) fn check_incoming<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expecting = std::any::type_name::<T>();
+
+    T::expected_incoming()
+        .iter()
+        .filter_map(|expectation| {
+            let count = (expectation.count)(world, entity);
+            let (relationship, min) = (expectation.relationship, expectation.min);
+
+            (count < min).then(|| ExpectationViolation {
+                entity,
+                expecting,
+                missing: relationship,
+                message: format!(
+                    "{expecting} expects at least {min} incoming {relationship} relationships but found {count} on entity {entity:?}"
+                ),
+                kind: ViolationKind::Other,
+                caller: None,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,141 @@
+//! Catching removals that leave an entity failing its expectations.
+
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+
+use bevy_ecs::component::ComponentId;
+use bevy_ecs::lifecycle::Remove;
+use bevy_ecs::observer::{Observer, On};
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::{DeferredWorld, World};
+
+use crate::{
+    config, has_component, report, scheduled, validation_enabled, ExpectComponents,
+    ExpectRegistration, ExpectationViolation, ViolationKind,
+};
+
+/// Expecting types indexed by each component they expect.
+#[derive(Resource, Default)]
+pub(crate) struct ExpectedBy(HashMap<TypeId, Vec<(TypeId, &'static str, &'static str)>>);
+
+impl ExpectedBy {
+    /// Indexes the components `registration` expects, skipping hooks-only
+    /// registrations, which carry no type of their own.
+    pub(crate) fn add(&mut self, registration: &ExpectRegistration) {
+        if registration.hooks_only {
+            return;
+        }
+        for (expected, name) in registration
+            .expected_components()
+            .iter()
+            .zip(registration.expected_component_names())
+        {
+            self.0.entry(*expected).or_default().push((
+                registration.type_id(),
+                registration.type_name(),
+                name,
+            ));
+        }
+    }
+}
+
+/// Expected components whose removal is already watched by an observer.
+#[derive(Resource, Default)]
+pub(crate) struct RemovalWatch(HashSet<TypeId>);
+
+/// Watches removals of every expected component registered in `world` that is
+/// not watched yet, with one observer per component.
+///
+/// Components can only be removed once registered, so components registered
+/// later are picked up when an expecting component is next inserted, or at the
+/// end of the frame.
+pub(crate) fn watch_expected(world: &mut World) {
+    let Some(index) = world.get_resource::<ExpectedBy>() else {
+        return;
+    };
+    let watched = world.get_resource::<RemovalWatch>();
+    if watched.is_some_and(|watched| watched.0.len() == index.0.len()) {
+        return;
+    }
+    let unwatched: Vec<(TypeId, ComponentId)> = index
+        .0
+        .keys()
+        .filter(|type_id| !watched.is_some_and(|watched| watched.0.contains(type_id)))
+        .filter_map(|type_id| Some((*type_id, world.components().get_id(*type_id)?)))
+        .collect();
+
+    for (type_id, component_id) in unwatched {
+        world.spawn(
+            Observer::new(move |remove: On<Remove>, world: DeferredWorld| {
+                check_removal(type_id, &remove, world);
+            })
+            .with_component(component_id),
+        );
+        world
+            .get_resource_or_init::<RemovalWatch>()
+            .0
+            .insert(type_id);
+    }
+}
+
+/// Queues [`watch_expected`] if any component `T` expects is not watched yet.
+pub(crate) fn watch_on_insert<T: ExpectComponents>(world: &mut DeferredWorld) {
+    let watched = world.get_resource::<RemovalWatch>();
+    if T::expected_components()
+        .iter()
+        .all(|type_id| watched.is_some_and(|watched| watched.0.contains(type_id)))
+    {
+        return;
+    }
+    world.commands().queue(watch_expected);
+}
+
+/// Reports a removal of the expected component `removed` while a component
+/// expecting it stays on the entity.
+///
+/// Despawns and removals that take the expecting component along are allowed,
+/// since they leave no component behind whose expectations fail. Like the
+/// insertion hooks, this defers to the scheduled pass when one is configured.
+fn check_removal(removed: TypeId, remove: &On<Remove>, mut world: DeferredWorld) {
+    if !validation_enabled(&world) || scheduled::is_scheduled(&world) {
+        return;
+    }
+
+    let entity = remove.entity;
+    let removed_with = |type_id: TypeId| {
+        world
+            .components()
+            .get_id(type_id)
+            .is_some_and(|id| remove.trigger().components.contains(&id))
+    };
+
+    let Some(index) = world.get_resource::<ExpectedBy>() else {
+        return;
+    };
+
+    let violations: Vec<ExpectationViolation> = index
+        .0
+        .get(&removed)
+        .into_iter()
+        .flatten()
+        .filter(|(expecting_id, _, _)| {
+            !removed_with(*expecting_id)
+                && has_component(&world, entity, *expecting_id)
+                && !config::is_suppressed(&world, *expecting_id)
+        })
+        .map(|(_, expecting, missing)| ExpectationViolation {
+            entity,
+            expecting,
+            missing,
+            message: format!(
+                "cannot remove {missing} while {expecting} is present on entity {entity:?}"
+            ),
+            kind: ViolationKind::Other,
+            caller: None,
+        })
+        .collect();
+
+    for violation in &violations {
+        report(&mut world, violation);
+    }
+}
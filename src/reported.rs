@@ -0,0 +1,47 @@
+//! Reporting the failures of per-frame checks once until they are fixed.
+
+use std::collections::HashSet;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::resource::Resource;
+
+use crate::ExpectationViolation;
+
+/// Failures a per-frame check has already reported, so an unchanged failure is
+/// not reported again every frame.
+///
+/// Failures are told apart by entity, expecting type and message, so a failure
+/// whose details change, such as a different child or count, is reported anew.
+#[derive(Default)]
+pub(crate) struct ReportedFailures {
+    reported: HashSet<(Entity, &'static str, String)>,
+}
+
+impl ReportedFailures {
+    /// Forgets reported failures that are no longer present, and returns the
+    /// failures of this frame that have not been reported yet.
+    pub(crate) fn unreported<'v>(
+        &mut self,
+        violations: &'v [ExpectationViolation],
+    ) -> Vec<&'v ExpectationViolation> {
+        let current: HashSet<_> = violations.iter().map(key).collect();
+        self.reported.retain(|failure| current.contains(failure));
+
+        violations
+            .iter()
+            .filter(|violation| self.reported.insert(key(violation)))
+            .collect()
+    }
+}
+
+/// Failures already reported by the per-frame pass over registered types.
+#[derive(Resource, Default)]
+pub(crate) struct DeferredReported(pub(crate) ReportedFailures);
+
+fn key(violation: &ExpectationViolation) -> (Entity, &'static str, String) {
+    (
+        violation.entity,
+        violation.expecting,
+        violation.message.clone(),
+    )
+}
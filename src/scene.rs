@@ -0,0 +1,168 @@
+//! Auditing freshly spawned scenes against registered expectations.
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::hierarchy::Children;
+use bevy_ecs::world::World;
+
+use crate::validation::entity_violations;
+use crate::{lookup, ExpectationViolation};
+
+/// Validates `root` and all of its descendants against every registered expectation.
+///
+/// Intended to be called right after a scene finishes spawning, to audit just the
+/// new subtree rather than the whole world. Works with or without the plugin and
+/// never panics; violations are returned for the caller to handle.
+///
+/// ```rust,ignore
+/// let violations = validate_scene_root(world, scene_root);
+/// for violation in &violations {
+///     error!("{violation}");
+/// }
+/// ```
+#[must_use]
+pub fn validate_scene_root(world: &World, root: Entity) -> Vec<ExpectationViolation> {
+    let mut violations = Vec::new();
+    let mut stack = vec![root];
+
+    while let Some(entity) = stack.pop() {
+        violations.extend(entity_violations(world, entity));
+
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter());
+        }
+    }
+
+    violations
+}
+
+/// Validates every entity in a scene's world against every registered expectation.
+///
+/// Bevy scenes hold their own `World`. Editors that build scenes in memory can
+/// audit that world before writing it into the main world, so broken authored
+/// content is caught before it reaches the game. The scene world needs no plugin
+/// or configuration, and violations are returned rather than reported.
+///
+/// ```rust,ignore
+/// let violations = validate_scene_world(&scene.world);
+/// if violations.is_empty() {
+///     scene.write_to_world(world, &mut entity_map)?;
+/// }
+/// ```
+#[must_use]
+pub fn validate_scene_world(scene_world: &World) -> Vec<ExpectationViolation> {
+    lookup::installed(scene_world)
+        .flat_map(|registration| registration.collect_violations(scene_world))
+        .collect()
+}
+
+/// Violations found in scenes spawned by Bevy's scene spawner, keyed by the
+/// invalid entity.
+///
+/// Filled when [`ExpectedComponentsPlugin::batch_scene_validation`](crate::ExpectedComponentsPlugin::batch_scene_validation)
+/// is enabled. Each scene instance is validated once it is ready, so one bad
+/// entity no longer stops the whole load, and the report can be shown to the
+/// user instead:
+///
+/// ```rust,ignore
+/// fn show_scene_errors(report: Res<SceneValidationReport>) {
+///     if !report.is_empty() {
+///         warn!("your scene has {} invalid entities", report.entities.len());
+///     }
+/// }
+/// ```
+#[cfg(feature = "scene")]
+#[derive(bevy_ecs::resource::Resource, Default, Debug)]
+pub struct SceneValidationReport {
+    /// Every violation found, grouped by the entity that fails it.
+    pub entities: std::collections::HashMap<Entity, Vec<ExpectationViolation>>,
+}
+
+#[cfg(feature = "scene")]
+impl SceneValidationReport {
+    /// Removes every recorded violation.
+    pub fn clear(&mut self) {
+        self.entities.clear();
+    }
+
+    /// Returns true if no violations have been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+/// Pauses validation around Bevy's scene spawner and validates each scene
+/// instance once it is ready.
+#[cfg(feature = "scene")]
+pub(crate) fn install_batch_validation(app: &mut bevy_app::App) {
+    use bevy_ecs::schedule::IntoScheduleConfigs;
+    use bevy_scene::SceneSpawnerSystems;
+
+    app.init_resource::<SceneValidationReport>();
+    app.add_systems(
+        bevy_app::SpawnScene,
+        (
+            pause_for_scenes.before(SceneSpawnerSystems::Spawn),
+            resume_after_scenes.after(SceneSpawnerSystems::Spawn),
+        ),
+    );
+    app.add_observer(record_scene);
+}
+
+/// The validation state to restore once the scene spawner has run.
+#[cfg(feature = "scene")]
+#[derive(bevy_ecs::resource::Resource)]
+struct PausedForScenes(Option<crate::ValidationEnabled>);
+
+/// Pauses insertion-time validation while the scene spawner writes scenes
+/// into the world.
+#[cfg(feature = "scene")]
+fn pause_for_scenes(world: &mut World) {
+    let previous = world.remove_resource::<crate::ValidationEnabled>();
+    world.insert_resource(PausedForScenes(previous));
+    world.insert_resource(crate::ValidationEnabled(false));
+}
+
+/// Restores the validation state saved by [`pause_for_scenes`].
+#[cfg(feature = "scene")]
+fn resume_after_scenes(world: &mut World) {
+    let Some(PausedForScenes(previous)) = world.remove_resource::<PausedForScenes>() else {
+        return;
+    };
+    match previous {
+        Some(enabled) => world.insert_resource(enabled),
+        None => {
+            world.remove_resource::<crate::ValidationEnabled>();
+        }
+    }
+}
+
+/// Validates the entities of a scene instance once it is ready, recording
+/// violations in [`SceneValidationReport`].
+///
+/// The entity the scene was spawned under was validated when it was added,
+/// so only its descendants are checked.
+#[cfg(feature = "scene")]
+#[allow(clippy::needless_pass_by_value)] // Bevy observer params are passed by value
+fn record_scene(
+    ready: bevy_ecs::observer::On<bevy_scene::SceneInstanceReady>,
+    mut world: bevy_ecs::world::DeferredWorld,
+) {
+    let violations: Vec<ExpectationViolation> = world
+        .get::<Children>(ready.entity)
+        .into_iter()
+        .flat_map(|children| children.iter())
+        .flat_map(|&child| validate_scene_root(&world, child))
+        .collect();
+
+    let Some(mut report) = world.get_resource_mut::<SceneValidationReport>() else {
+        return;
+    };
+    for violation in violations {
+        report
+            .entities
+            .entry(violation.entity)
+            .or_default()
+            .push(violation);
+    }
+}
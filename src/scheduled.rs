@@ -0,0 +1,286 @@
+//! Validation as a scheduled system rather than in component hooks.
+
+use std::time::Duration;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::{Changed, QueryFilter, With};
+use bevy_ecs::resource::Resource;
+use bevy_ecs::schedule::SystemSet;
+use bevy_ecs::world::{DeferredWorld, World};
+use bevy_platform::time::Instant;
+
+use crate::config::{is_bevy_internal, is_suppressed, SkipBevyInternal};
+use crate::reported::DeferredReported;
+use crate::validation::{check_deferred, has_deferred};
+use crate::{
+    check_expectations, lookup, non_default, predicate, validation::report_all, validation_enabled,
+    ExpectComponents, ExpectRegistration, ExpectationViolation,
+};
+
+/// System set containing scheduled validation.
+///
+/// Order systems `.before(ValidationSystems)` so their commands are applied
+/// before validation runs.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidationSystems;
+
+/// Resource present when validation runs in a schedule instead of hooks.
+#[derive(Resource)]
+pub(crate) struct ScheduledValidation {
+    /// Only validate entities whose expecting component was added or changed
+    /// since the last pass.
+    pub(crate) changed_only: bool,
+}
+
+/// Caps how long scheduled validation may run each frame.
+///
+/// When the budget runs out, the pass stops and resumes from the same place next
+/// frame, so validating a huge world is spread across several frames instead of
+/// causing a hitch. At least one entity is checked per frame. The budget has no
+/// effect with [`validate_changed_only`](crate::ExpectedComponentsPlugin::validate_changed_only).
+///
+/// ```rust,ignore
+/// app.insert_resource(ValidationTimeBudget(Duration::from_micros(500)));
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationTimeBudget(pub Duration);
+
+/// Gives entities this many change ticks after their expecting component is
+/// added to become complete, before scheduled validation reports them.
+///
+/// Entities assembled over several frames are not reported while still within
+/// the window, but ones left half-built past it are. The window is measured in
+/// ECS change ticks rather than time, so it is deterministic.
+///
+/// ```rust,ignore
+/// app.insert_resource(CompletionDeadline(1_000));
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompletionDeadline(pub u32);
+
+/// Where a budgeted pass stopped, as a registration index and an entity offset
+/// into that registration's entities.
+#[derive(Resource, Default)]
+pub(crate) struct ValidationCursor {
+    registration: usize,
+    entity: usize,
+}
+
+/// Returns true if validation runs in a schedule, so hooks should not validate.
+pub(crate) fn is_scheduled(world: &World) -> bool {
+    world.contains_resource::<ScheduledValidation>()
+}
+
+/// Returns every violation of `T`'s expectations across entities with `T`.
+///
+/// In changed-only mode, entities whose `T` is unchanged since the last pass
+/// are skipped, unless one of `T`'s non-default or predicate components was
+/// mutated.
+pub(crate) fn collect_violations_for<T: ExpectComponents>(
+    world: &World,
+) -> Vec<ExpectationViolation> {
+    let changed_only = world
+        .get_resource::<ScheduledValidation>()
+        .is_some_and(|scheduled| scheduled.changed_only);
+
+    let entities = if changed_only {
+        let mut entities = entities_matching::<Changed<T>>(world);
+        if revalidates_mutations::<T>() {
+            let mutated: Vec<Entity> = entities_matching::<With<T>>(world)
+                .into_iter()
+                .filter(|entity| !entities.contains(entity))
+                .filter(|&entity| {
+                    non_default::any_mutated::<T>(world, entity)
+                        || predicate::any_mutated::<T>(world, entity)
+                })
+                .collect();
+            entities.extend(mutated);
+        }
+        entities
+    } else {
+        entities_matching::<With<T>>(world)
+    };
+
+    entities
+        .into_iter()
+        .flat_map(|entity| check_expectations::<T>(world, entity))
+        .collect()
+}
+
+/// Returns the entities matching `F`, or none if its components were never registered.
+fn entities_matching<F: QueryFilter>(world: &World) -> Vec<Entity> {
+    world
+        .try_query_filtered::<Entity, F>()
+        .map(|mut query| query.iter(world).collect())
+        .unwrap_or_default()
+}
+
+/// Returns the registrations validated in `world`: those the plugin installed,
+/// or every registered type without the plugin, leaving out suppressed types
+/// and, when the plugin was configured to skip them, Bevy's own types.
+pub(crate) fn validated_registrations(world: &World) -> Vec<ExpectRegistration> {
+    let skip_internal = world.contains_resource::<SkipBevyInternal>();
+    let validated = |registration: &&ExpectRegistration| {
+        let internal = skip_internal && is_bevy_internal(registration.type_name());
+        !internal && !is_suppressed(world, registration.type_id())
+    };
+
+    lookup::installed(world)
+        .filter(validated)
+        .copied()
+        .collect()
+}
+
+/// Returns every violation across entities with a registered expecting component.
+///
+/// This is a plain system, so a full validation pass can be run on demand without
+/// installing the plugin. Violations are returned rather than reported, and
+/// [`ValidationEnabled`](crate::ValidationEnabled) is ignored.
+///
+/// ```rust,ignore
+/// use bevy_ecs::system::RunSystemOnce;
+///
+/// let violations = world.run_system_once(validation_system)?;
+/// assert!(violations.is_empty());
+/// ```
+#[must_use]
+pub fn validation_system(world: &World) -> Vec<ExpectationViolation> {
+    validated_registrations(world)
+        .into_iter()
+        .flat_map(|registration| registration.collect_violations(world))
+        .collect()
+}
+
+/// Validates every entity with a registered expecting component.
+pub(crate) fn validate_scheduled(world: &mut World) {
+    if !validation_enabled(world) {
+        return;
+    }
+
+    let changed_only = world
+        .get_resource::<ScheduledValidation>()
+        .is_some_and(|scheduled| scheduled.changed_only);
+
+    let mut violations = match world.get_resource::<ValidationTimeBudget>() {
+        Some(&ValidationTimeBudget(budget)) if !changed_only => {
+            validate_within_budget(world, budget)
+        }
+        _ => validation_system(world),
+    };
+
+    if let Some(&CompletionDeadline(ticks)) = world.get_resource::<CompletionDeadline>() {
+        let now = world.read_change_tick().get();
+        violations.retain(|violation| {
+            added_tick(world, violation).is_none_or(|added| now.wrapping_sub(added) > ticks)
+        });
+    }
+
+    report_all(&mut DeferredWorld::from(world), &violations);
+}
+
+/// Returns the change tick at which the violation's expecting component was
+/// added to its entity.
+fn added_tick(world: &World, violation: &ExpectationViolation) -> Option<u32> {
+    let registration = lookup::installed_named(world, violation.expecting)?;
+    let id = world.components().get_id(registration.type_id())?;
+
+    world
+        .get_entity(violation.entity)
+        .ok()?
+        .get_change_ticks_by_id(id)
+        .map(|ticks| ticks.added.get())
+}
+
+/// Continues the pass from the saved [`ValidationCursor`] until `budget` is spent,
+/// returning the violations found along the way.
+fn validate_within_budget(world: &mut World, budget: Duration) -> Vec<ExpectationViolation> {
+    let start = Instant::now();
+    let registrations = validated_registrations(world);
+    let mut cursor = world
+        .remove_resource::<ValidationCursor>()
+        .unwrap_or_default();
+    let mut violations = Vec::new();
+    let mut checked = 0;
+
+    while let Some(registration) = registrations.get(cursor.registration) {
+        let entities = registration.entities(world);
+
+        while let Some(&entity) = entities.get(cursor.entity) {
+            if checked > 0 && start.elapsed() >= budget {
+                world.insert_resource(cursor);
+                return violations;
+            }
+
+            violations.extend(registration.check_entity(world, entity));
+            cursor.entity += 1;
+            checked += 1;
+        }
+
+        cursor.registration += 1;
+        cursor.entity = 0;
+    }
+
+    // The pass is complete, so the next frame starts from the beginning
+    world.insert_resource(ValidationCursor::default());
+    violations
+}
+
+/// Returns every entity with `T`.
+pub(crate) fn entities_with<T: ExpectComponents>(world: &World) -> Vec<Entity> {
+    entities_matching::<With<T>>(world)
+}
+
+/// Returns true if `T` has value expectations that an in-place mutation can
+/// break after insertion.
+fn revalidates_mutations<T: ExpectComponents>() -> bool {
+    !T::expected_non_default().is_empty() || !T::expected_predicates().is_empty()
+}
+
+/// Returns every violation of `T`'s per-frame expectations, which cannot be
+/// judged when `T` is inserted, including non-default components mutated back
+/// to their defaults and predicate components mutated to failing values.
+pub(crate) fn collect_deferred_violations_for<T: ExpectComponents>(
+    world: &World,
+) -> Vec<ExpectationViolation> {
+    let revalidate = revalidates_mutations::<T>();
+    if !has_deferred::<T>() && !revalidate {
+        return Vec::new();
+    }
+
+    entities_matching::<With<T>>(world)
+        .into_iter()
+        .flat_map(|entity| {
+            let mut violations = check_deferred::<T>(world, entity);
+            if revalidate {
+                violations.extend(non_default::check_mutated::<T>(world, entity));
+                violations.extend(predicate::check_mutated::<T>(world, entity));
+            }
+            violations
+        })
+        .collect()
+}
+
+/// Validates per-frame expectations when validation otherwise runs in hooks,
+/// reporting each failure once until it is fixed.
+///
+/// In scheduled mode [`validate_scheduled`] already covers them.
+pub(crate) fn validate_deferred(world: &mut World) {
+    if !validation_enabled(world) || is_scheduled(world) {
+        return;
+    }
+
+    let violations: Vec<ExpectationViolation> = validated_registrations(world)
+        .into_iter()
+        .flat_map(|registration| registration.collect_deferred_violations(world))
+        .collect();
+
+    let new: Vec<ExpectationViolation> = world
+        .resource_mut::<DeferredReported>()
+        .0
+        .unreported(&violations)
+        .into_iter()
+        .cloned()
+        .collect();
+
+    report_all(&mut DeferredWorld::from(world), &new);
+}
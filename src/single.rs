@@ -0,0 +1,139 @@
+//! Expectations on singleton entities, resolved by a single-entity query.
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::{QuerySingleError, With};
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::{DeferredWorld, World};
+
+use crate::reported::ReportedFailures;
+use crate::{has_component, report, validation_enabled, ExpectationViolation, ViolationKind};
+
+/// Checks a singleton expectation, reporting a missing singleton only if the
+/// flag is set.
+type SingleCheck = fn(&World, bool) -> Option<ExpectationViolation>;
+
+/// Registered singleton expectations, checked once per frame in the `Last` schedule.
+#[derive(Resource, Default)]
+pub(crate) struct SingleExpectations {
+    checks: Vec<SingleCheck>,
+    reported: ReportedFailures,
+    /// Frames checked so far, for the [`SingletonGracePeriod`].
+    frames: u32,
+}
+
+/// Gives singletons this many frames after startup to be spawned before a
+/// missing singleton is reported.
+///
+/// For apps that spawn their singletons after a loading phase. Several entities
+/// claiming to be the singleton, or a singleton without its expected component,
+/// are still reported straight away.
+///
+/// ```rust,ignore
+/// app.insert_resource(SingletonGracePeriod(60));
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SingletonGracePeriod(pub u32);
+
+/// Registers an expectation that exactly one entity has the marker `M`, and that
+/// it also has `E`.
+///
+/// [`ExpectedComponentsPlugin`](crate::ExpectedComponentsPlugin) resolves the
+/// singleton at the end of each frame and reports a missing singleton, several
+/// entities claiming to be it, or a singleton without `E`, each with its own
+/// message. Each failure is reported once, until it is fixed. A singleton
+/// spawned after startup can be given time with a [`SingletonGracePeriod`].
+///
+/// ```rust,ignore
+/// register_single_expectation::<Player, Health>(app.world_mut());
+/// ```
+pub fn register_single_expectation<M: Component, E: Component>(world: &mut World) {
+    world
+        .get_resource_or_init::<SingleExpectations>()
+        .checks
+        .push(check_single::<M, E>);
+}
+
+/// Returns the violation of the singleton expectation that `M`'s entity has `E`,
+/// if any, leaving out a missing singleton unless `report_absent` is set.
+fn check_single<M: Component, E: Component>(
+    world: &World,
+    report_absent: bool,
+) -> Option<ExpectationViolation> {
+    let expecting = std::any::type_name::<M>();
+    let missing = std::any::type_name::<E>();
+    let violation = |entity, message| ExpectationViolation {
+        entity,
+        expecting,
+        missing,
+        message,
+        kind: ViolationKind::Other,
+        caller: None,
+    };
+
+    let result = match world.try_query_filtered::<Entity, With<M>>() {
+        Some(mut query) => query.single(world),
+        None => Err(QuerySingleError::NoEntities(
+            std::any::type_name::<M>().into(),
+        )),
+    };
+
+    match result {
+        Ok(entity) if has_component(world, entity, std::any::TypeId::of::<E>()) => None,
+        Ok(entity) => Some(violation(
+            entity,
+            format!(
+                "{expecting} expects its singleton entity to have {missing}, but it was not found on entity {entity:?}"
+            ),
+        )),
+        Err(QuerySingleError::NoEntities(_)) if !report_absent => None,
+        Err(QuerySingleError::NoEntities(_)) => Some(violation(
+            Entity::PLACEHOLDER,
+            format!("{expecting} expects exactly one entity with it, but found none"),
+        )),
+        Err(QuerySingleError::MultipleEntities(_)) => {
+            let count = world
+                .try_query_filtered::<Entity, With<M>>()
+                .map_or(0, |mut query| query.iter(world).count());
+            Some(violation(
+                Entity::PLACEHOLDER,
+                format!("{expecting} expects exactly one entity with it, but found {count}"),
+            ))
+        }
+    }
+}
+
+/// Checks every registered singleton expectation, reporting each failure once.
+///
+/// A failure is reported again only after it has been fixed and then recurs.
+pub(crate) fn validate_single_expectations(world: &mut World) {
+    if !validation_enabled(world) {
+        return;
+    }
+
+    let grace = world
+        .get_resource::<SingletonGracePeriod>()
+        .map_or(0, |grace| grace.0);
+    let Some(mut expectations) = world.get_resource_mut::<SingleExpectations>() else {
+        return;
+    };
+    expectations.frames = expectations.frames.saturating_add(1);
+    let report_absent = expectations.frames > grace;
+
+    let expectations = world.resource::<SingleExpectations>();
+    let violations: Vec<ExpectationViolation> = expectations
+        .checks
+        .iter()
+        .filter_map(|check| check(world, report_absent))
+        .collect();
+
+    let new = world
+        .resource_mut::<SingleExpectations>()
+        .reported
+        .unreported(&violations);
+
+    let mut world = DeferredWorld::from(world);
+    for violation in new {
+        report(&mut world, violation);
+    }
+}
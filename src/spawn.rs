@@ -0,0 +1,247 @@
+//! Validating a bundle against registered expectations before spawning it.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use bevy_ecs::bundle::Bundle;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, EntityCommands};
+use bevy_ecs::world::{EntityWorldMut, World};
+
+use crate::{
+    lookup, registry, validate_entity, ExpectRegistration, ExpectationReport, ExpectationViolation,
+    ValidationEnabled,
+};
+
+/// Spawning that checks a bundle's expectations up front and returns an error
+/// instead of spawning an entity that would fail them.
+///
+/// The check runs synchronously at the call, without hooks, so it works with or
+/// without the plugin. Only expectations on which components are present can be
+/// judged before spawning; relationship, child and value expectations are left to
+/// the plugin.
+///
+/// ```rust,ignore
+/// match commands.spawn_checked((PhysicsBody, Transform::default())) {
+///     Ok(entity) => { /* ... */ }
+///     Err(report) => error!("{report}"),
+/// }
+/// ```
+pub trait ExpectSpawnExt {
+    /// The handle returned for a spawned entity.
+    type Spawned<'a>
+    where
+        Self: 'a;
+
+    /// Spawns `bundle` if it satisfies the expectations of every expecting
+    /// component in it, counting components it requires.
+    ///
+    /// # Errors
+    ///
+    /// Returns every failed expectation, without spawning, if the bundle fails any.
+    fn spawn_checked<B: Bundle>(
+        &mut self,
+        bundle: B,
+    ) -> Result<Self::Spawned<'_>, ExpectationReport>;
+
+    /// Spawns `bundle` and returns its entity if it satisfies every registered
+    /// expectation, or the violations if it does not, without reporting them.
+    ///
+    /// On a `World` the entity is spawned with the hooks' validation paused and
+    /// then checked in full, including value, resource and hierarchy
+    /// expectations, and despawned again if it fails. `Commands` cannot see the
+    /// world, so only expectations on which components are present are checked,
+    /// as in [`Self::spawn_checked`].
+    ///
+    /// ```rust,ignore
+    /// match world.spawn_validated(scene_entity_bundle) {
+    ///     Ok(entity) => loaded.push(entity),
+    ///     Err(violations) => errors.extend(violations),
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns every failed expectation if the bundle fails any.
+    fn spawn_validated<B: Bundle>(
+        &mut self,
+        bundle: B,
+    ) -> Result<Entity, Vec<ExpectationViolation>>;
+
+    /// Spawns `bundle` and panics at the call if it fails any registered
+    /// expectation, regardless of the configured failure mode.
+    ///
+    /// Hook-based validation runs inside Bevy's insert, so its panic location is
+    /// in this crate and it follows the configured [`FailureMode`](crate::FailureMode).
+    /// This instead spawns with the hooks' validation paused and validates
+    /// inline before returning, so the panic names the caller's spawn line and a
+    /// test fails deterministically. On a `World` every expectation is checked,
+    /// as in [`Self::spawn_validated`]; `Commands` check only which components
+    /// are present, before queueing the spawn.
+    ///
+    /// ```rust,ignore
+    /// let entity = world.spawn_expecting((PhysicsBody, Position, Velocity)).id();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics with every failed expectation if the bundle fails any.
+    #[track_caller]
+    fn spawn_expecting<B: Bundle>(&mut self, bundle: B) -> Self::Spawned<'_>;
+}
+
+impl ExpectSpawnExt for World {
+    type Spawned<'a> = EntityWorldMut<'a>;
+
+    fn spawn_checked<B: Bundle>(
+        &mut self,
+        bundle: B,
+    ) -> Result<EntityWorldMut<'_>, ExpectationReport> {
+        check_bundle::<B>(lookup::installed(self))?;
+        Ok(self.spawn(bundle))
+    }
+
+    fn spawn_validated<B: Bundle>(
+        &mut self,
+        bundle: B,
+    ) -> Result<Entity, Vec<ExpectationViolation>> {
+        let entity = spawn_unvalidated(self, bundle);
+
+        if let Err(violations) = validate_entity(self, entity) {
+            self.despawn(entity);
+            return Err(violations);
+        }
+        Ok(entity)
+    }
+
+    #[track_caller]
+    fn spawn_expecting<B: Bundle>(&mut self, bundle: B) -> EntityWorldMut<'_> {
+        let entity = spawn_unvalidated(self, bundle);
+
+        if let Err(violations) = validate_entity(self, entity) {
+            panic!("{}", ExpectationReport { violations });
+        }
+        self.entity_mut(entity)
+    }
+}
+
+impl ExpectSpawnExt for Commands<'_, '_> {
+    type Spawned<'a>
+        = EntityCommands<'a>
+    where
+        Self: 'a;
+
+    fn spawn_checked<B: Bundle>(
+        &mut self,
+        bundle: B,
+    ) -> Result<EntityCommands<'_>, ExpectationReport> {
+        validate_bundle::<B>()?;
+        Ok(self.spawn(bundle))
+    }
+
+    fn spawn_validated<B: Bundle>(
+        &mut self,
+        bundle: B,
+    ) -> Result<Entity, Vec<ExpectationViolation>> {
+        self.spawn_checked(bundle)
+            .map(|entity| entity.id())
+            .map_err(|report| report.violations)
+    }
+
+    #[track_caller]
+    fn spawn_expecting<B: Bundle>(&mut self, bundle: B) -> EntityCommands<'_> {
+        if let Err(report) = validate_bundle::<B>() {
+            panic!("{report}");
+        }
+        self.spawn(bundle)
+    }
+}
+
+/// Spawns `bundle` with the hooks' validation paused, restoring the previous
+/// [`ValidationEnabled`] setting afterwards.
+fn spawn_unvalidated<B: Bundle>(world: &mut World, bundle: B) -> Entity {
+    let enabled = world.remove_resource::<ValidationEnabled>();
+    world.insert_resource(ValidationEnabled(false));
+    let entity = world.spawn(bundle).id();
+    match enabled {
+        Some(enabled) => world.insert_resource(enabled),
+        None => {
+            world.remove_resource::<ValidationEnabled>();
+        }
+    }
+    entity
+}
+
+/// Checks that the components `B` would spawn, including required components,
+/// satisfy the presence expectations of every expecting component among them.
+///
+/// Nothing is spawned and no world is needed, so a bundle can be checked at
+/// its definition or in a unit test, before any entity uses it. This is the
+/// check [`ExpectSpawnExt::spawn_checked`] runs before spawning. Without a
+/// world only types collected by `inventory` or submitted through
+/// [`register_all_expectations!`](crate::register_all_expectations) are
+/// known; on a `World`,
+/// `spawn_checked` checks the types the plugin installed instead, including
+/// those from an [`ExpectationRegistry`](crate::ExpectationRegistry).
+///
+/// ```rust,ignore
+/// #[test]
+/// fn vehicle_bundle_is_complete() {
+///     validate_bundle::<VehicleBundle>().unwrap();
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an [`ExpectationReport`] listing every expected component missing
+/// from the bundle.
+pub fn validate_bundle<B: Bundle>() -> Result<(), ExpectationReport> {
+    check_bundle::<B>(registry::collected_registrations())
+}
+
+/// Checks `B` against `registrations`, as [`validate_bundle`] does against
+/// every collected type.
+fn check_bundle<'a, B: Bundle>(
+    registrations: impl Iterator<Item = &'a ExpectRegistration>,
+) -> Result<(), ExpectationReport> {
+    let components = bundle_components::<B>();
+    let violations: Vec<_> = registrations
+        .flat_map(|registration| registration.check_bundle(&components))
+        .collect();
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(ExpectationReport { violations })
+    }
+}
+
+/// Returns the `TypeId`s of every component spawning `B` inserts, including
+/// required components.
+///
+/// Commands have no world access, so the bundle is registered in a scratch
+/// world. The result is cached per bundle type.
+fn bundle_components<B: Bundle>() -> Vec<TypeId> {
+    static CACHE: OnceLock<Mutex<HashMap<TypeId, Vec<TypeId>>>> = OnceLock::new();
+
+    let mut cache = CACHE
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    cache
+        .entry(TypeId::of::<B>())
+        .or_insert_with(|| {
+            let mut world = World::new();
+            let ids = world
+                .register_bundle::<B>()
+                .contributed_components()
+                .to_vec();
+            let components = world.components();
+            ids.into_iter()
+                .filter_map(|id| components.get_info(id)?.type_id())
+                .collect()
+        })
+        .clone()
+}
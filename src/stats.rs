@@ -0,0 +1,71 @@
+//! Measuring how much validation each expecting type costs.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::{DeferredWorld, World};
+use bevy_platform::time::Instant;
+
+use crate::ExpectComponents;
+
+/// Validation counts and time spent for one expecting type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TypeStats {
+    /// Number of times the type's expectations were validated.
+    pub validations: u64,
+    /// Total time spent validating the type's expectations.
+    pub total_time: Duration,
+}
+
+/// Records per-type validation counts and timings.
+///
+/// Stats are opt-in: insert this resource to enable them, then call
+/// [`validation_stats_by_type`] to see which expecting types dominate
+/// validation cost. Validations run by `on_add` hooks are recorded.
+///
+/// ```rust,ignore
+/// app.init_resource::<ValidationStats>();
+/// app.add_plugins(ExpectedComponentsPlugin);
+///
+/// // ... run the app ...
+///
+/// for (name, stats) in validation_stats_by_type(app.world()) {
+///     println!("{name}: {} validations in {:?}", stats.validations, stats.total_time);
+/// }
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct ValidationStats {
+    by_type: HashMap<&'static str, TypeStats>,
+}
+
+/// Returns the current time if stats are enabled.
+pub(crate) fn start_timing(world: &World) -> Option<Instant> {
+    world
+        .contains_resource::<ValidationStats>()
+        .then(Instant::now)
+}
+
+/// Records one validation of `T` that began at `started`, if stats are enabled.
+pub(crate) fn record<T: ExpectComponents>(world: &mut DeferredWorld, started: Option<Instant>) {
+    let (Some(started), Some(mut stats)) = (started, world.get_resource_mut::<ValidationStats>())
+    else {
+        return;
+    };
+
+    let entry = stats.by_type.entry(std::any::type_name::<T>()).or_default();
+    entry.validations += 1;
+    entry.total_time += started.elapsed();
+}
+
+/// Returns validation stats keyed by expecting type name.
+///
+/// Requires [`ValidationStats`] to be present. Without it nothing is recorded,
+/// so the map is empty.
+#[must_use]
+pub fn validation_stats_by_type(world: &World) -> HashMap<&'static str, TypeStats> {
+    world
+        .get_resource::<ValidationStats>()
+        .map(|stats| stats.by_type.clone())
+        .unwrap_or_default()
+}
@@ -0,0 +1,48 @@
+//! Reading whether entities satisfy their expectations from inside a query.
+
+use bevy_ecs::query::QueryData;
+use bevy_ecs::world::EntityRef;
+
+use crate::validation::check_expectations_on;
+use crate::ExpectComponents;
+
+/// Query data yielding whether each entity with `T` currently satisfies `T`'s
+/// expectations.
+///
+/// Only expectations on which components are present are checked, as in
+/// [`assert_entity_expectations`](crate::assert_entity_expectations). The query
+/// reads every component of matched entities, so it cannot be combined with
+/// mutable access in the same query.
+///
+/// ```rust,ignore
+/// fn report(bodies: Query<(Entity, ExpectationStatus<PhysicsBody>)>) {
+///     for (entity, status) in &bodies {
+///         if !status.is_satisfied() {
+///             warn!("{entity} is missing {:?}", status.missing());
+///         }
+///     }
+/// }
+/// ```
+#[derive(QueryData)]
+pub struct ExpectationStatus<T: ExpectComponents> {
+    entity: EntityRef<'static>,
+    // Restricts the query to entities with `T`
+    expecting: &'static T,
+}
+
+impl<T: ExpectComponents> ExpectationStatusItem<'_, '_, T> {
+    /// Returns true if the entity satisfies every presence expectation of `T`.
+    #[must_use]
+    pub fn is_satisfied(&self) -> bool {
+        check_expectations_on::<T>(&self.entity).is_empty()
+    }
+
+    /// Returns the names of the components `T` expects that the entity is missing.
+    #[must_use]
+    pub fn missing(&self) -> Vec<&'static str> {
+        check_expectations_on::<T>(&self.entity)
+            .into_iter()
+            .map(|violation| violation.missing)
+            .collect()
+    }
+}
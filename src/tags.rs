@@ -0,0 +1,99 @@
+//! Enforcing only the expectations whose tags and groups are enabled.
+
+use std::collections::HashSet;
+
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::World;
+
+use crate::{lookup, ExpectationViolation};
+
+/// Restricts enforcement to expectations tagged with one of these tags.
+///
+/// Tag expectations with `#[expects(Collider, tags = ["physics"])]`. When this
+/// resource is present, failures of expectations without an enabled tag,
+/// including untagged ones, are not reported. When absent, every expectation is
+/// enforced.
+///
+/// ```rust,ignore
+/// // Only enforce physics contracts while debugging physics
+/// app.insert_resource(EnabledTags::new(["physics"]));
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct EnabledTags(pub HashSet<String>);
+
+impl EnabledTags {
+    /// Creates a set enabling each of `tags`.
+    pub fn new(tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self(tags.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns true if `tag` is enabled.
+    #[must_use]
+    pub fn contains(&self, tag: &str) -> bool {
+        self.0.contains(tag)
+    }
+}
+
+/// Named groups of expectations that can be switched off together.
+///
+/// Put expectations in a group with `#[expects(RigidBody, Collider, group = "physics")]`.
+/// A group is a tag, so it also works with [`EnabledTags`]. Every group is
+/// enabled until disabled here, and an expectation in any disabled group is not
+/// reported. The plugin inserts this resource.
+///
+/// ```rust,ignore
+/// // A headless server has nothing to render
+/// app.world_mut()
+///     .resource_mut::<ExpectationGroups>()
+///     .disable_group("rendering");
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExpectationGroups {
+    disabled: HashSet<String>,
+}
+
+impl ExpectationGroups {
+    /// Enforces the expectations in `group` again.
+    pub fn enable_group(&mut self, group: &str) {
+        self.disabled.remove(group);
+    }
+
+    /// Stops enforcing the expectations in `group`.
+    pub fn disable_group(&mut self, group: impl Into<String>) {
+        self.disabled.insert(group.into());
+    }
+
+    /// Returns true unless `group` has been disabled.
+    #[must_use]
+    pub fn is_group_enabled(&self, group: &str) -> bool {
+        !self.disabled.contains(group)
+    }
+}
+
+/// Returns true if `violation`'s expectation should be reported under the
+/// current [`EnabledTags`] and [`ExpectationGroups`].
+pub(crate) fn is_enforced(world: &World, violation: &ExpectationViolation) -> bool {
+    let tags = tags_of(world, violation.expecting, violation.missing);
+
+    if let Some(groups) = world.get_resource::<ExpectationGroups>() {
+        if !tags.iter().all(|tag| groups.is_group_enabled(tag)) {
+            return false;
+        }
+    }
+
+    world
+        .get_resource::<EnabledTags>()
+        .is_none_or(|enabled| tags.iter().any(|tag| enabled.contains(tag)))
+}
+
+/// Returns the tags `expecting` declared on its expectation of `missing`.
+fn tags_of(world: &World, expecting: &str, missing: &str) -> &'static [&'static str] {
+    lookup::installed_named(world, expecting)
+        .and_then(|registration| {
+            registration
+                .expected_tags()
+                .iter()
+                .find(|(name, _)| *name == missing)
+        })
+        .map_or(&[], |(_, tags)| tags)
+}
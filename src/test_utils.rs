@@ -0,0 +1,72 @@
+//! Assertions for testing expectation setups.
+
+use std::fmt::Write;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::world::World;
+
+use crate::{validate_entity, ExpectComponents, ExpectRegistration};
+
+/// Panics unless `entity` satisfies every registered expectation.
+///
+/// The panic lists what is missing and what the entity has, so a failing test
+/// shows the difference at a glance:
+///
+/// ```text
+/// entity 5v0 fails its expectations
+///   missing:
+///     - my_game::Velocity, expected by my_game::PhysicsBody
+///   present:
+///     + my_game::PhysicsBody
+///     + my_game::Position
+/// ```
+///
+/// Component names come from Bevy's `debug` feature.
+///
+/// # Panics
+///
+/// Panics if any expectation on `entity` is violated.
+pub fn assert_expectations_satisfied(world: &World, entity: Entity) {
+    let Err(violations) = validate_entity(world, entity) else {
+        return;
+    };
+
+    let mut message = format!("entity {entity:?} fails its expectations\n  missing:");
+    for violation in &violations {
+        let _ = write!(
+            message,
+            "\n    - {}, expected by {}",
+            violation.missing, violation.expecting
+        );
+    }
+    if let Ok(components) = world.inspect_entity(entity) {
+        message.push_str("\n  present:");
+        for info in components {
+            let _ = write!(message, "\n    + {}", info.name());
+        }
+    }
+
+    panic!("{message}");
+}
+
+/// Panics unless `entity` violates at least one of `T`'s expectations.
+///
+/// Use it to test that an invalid setup is caught without relying on
+/// `#[should_panic]`.
+///
+/// # Panics
+///
+/// Panics if `entity` satisfies `T`'s expectations, or does not have `T`.
+pub fn assert_expectation_violated<T: ExpectComponents>(world: &World, entity: Entity) {
+    let expecting = std::any::type_name::<T>();
+    assert!(
+        world.get::<T>(entity).is_some(),
+        "expected entity {entity:?} to violate {expecting}'s expectations, but it does not have {expecting}"
+    );
+    assert!(
+        !ExpectRegistration::of::<T>()
+            .check_entity(world, entity)
+            .is_empty(),
+        "expected entity {entity:?} to violate {expecting}'s expectations, but it satisfies them"
+    );
+}
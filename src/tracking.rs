@@ -0,0 +1,64 @@
+//! Tracking which expecting components have ever been spawned.
+
+use std::any::TypeId;
+use std::collections::HashSet;
+
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::{DeferredWorld, World};
+
+use crate::{introspection, ExpectComponents, ExpectRegistration};
+
+/// Records which expecting component types have been added to any entity.
+///
+/// Tracking is opt-in: insert this resource to enable it, then call
+/// [`never_spawned_types`] to find expectations that were never exercised.
+///
+/// ```rust,ignore
+/// app.init_resource::<SpawnTracker>();
+/// app.add_plugins(ExpectedComponentsPlugin);
+///
+/// // ... run the app ...
+///
+/// for name in never_spawned_types(app.world()) {
+///     println!("{name} was never spawned");
+/// }
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct SpawnTracker {
+    spawned: HashSet<TypeId>,
+}
+
+impl SpawnTracker {
+    /// Returns true if a component with the given `TypeId` has been added.
+    #[must_use]
+    pub fn was_spawned(&self, type_id: TypeId) -> bool {
+        self.spawned.contains(&type_id)
+    }
+}
+
+/// Records that `T` was added, if tracking is enabled.
+pub(crate) fn record_spawn<T: ExpectComponents>(world: &mut DeferredWorld) {
+    if let Some(mut tracker) = world.get_resource_mut::<SpawnTracker>() {
+        tracker.spawned.insert(TypeId::of::<T>());
+    }
+}
+
+/// Returns the names of registered expecting types that have never been added
+/// to an entity, sorted alphabetically.
+///
+/// Requires [`SpawnTracker`] to be present. Without it no spawns are observed,
+/// so every registered type is reported.
+#[must_use]
+pub fn never_spawned_types(world: &World) -> Vec<&'static str> {
+    let tracker = world.get_resource::<SpawnTracker>();
+
+    let mut names: Vec<&'static str> = introspection::collected()
+        .filter(|registration| {
+            !tracker.is_some_and(|tracker| tracker.was_spawned(registration.type_id()))
+        })
+        .map(ExpectRegistration::type_name)
+        .collect();
+
+    names.sort_unstable();
+    names
+}
@@ -0,0 +1,691 @@
+//! Checking entities against their expectations and reporting failures.
+
+use std::any::TypeId;
+use std::panic::Location;
+
+use bevy_ecs::component::StorageType;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::lifecycle::HookContext;
+use bevy_ecs::world::{DeferredWorld, EntityRef, World};
+
+use crate::{
+    add_order, children, component_ids, computed, config, config::WarnedPatterns,
+    current_failure_mode, current_panic_behavior, file_sink, installed, lookup, non_default,
+    predicate, rate_limit, recovery, related, removal, scheduled, stats, tags, tracking,
+    validation_enabled, variant, verbose, ExpectComponents, ExpectRegistration,
+    ExpectationViolation, FailureMode, PanicBehavior, ValidationGate, ValidationReport,
+    ViolationHandler, ViolationKind,
+};
+
+/// Validation hook called when a component with expectations is inserted.
+#[allow(clippy::needless_pass_by_value)] // Bevy hook signature requires owned DeferredWorld
+pub(crate) fn validate_expected<T: ExpectComponents>(mut world: DeferredWorld, ctx: HookContext) {
+    tracking::record_spawn::<T>(&mut world);
+    removal::watch_on_insert::<T>(&mut world);
+
+    if !validation_enabled(&world)
+        || scheduled::is_scheduled(&world)
+        || config::is_suppressed(&world, TypeId::of::<T>())
+    {
+        return;
+    }
+
+    let entity = ctx.entity;
+
+    if world
+        .get_resource::<ValidationGate>()
+        .is_some_and(|gate| !gate.should_validate(&world, entity))
+    {
+        return;
+    }
+
+    let started = stats::start_timing(&world);
+    validate_on_insert::<T>(&mut world, entity, ctx.caller.into_option());
+    stats::record::<T>(&mut world, started);
+}
+
+/// Checks `entity` against `T`'s expectations as `T` is inserted, reporting
+/// every failure.
+///
+/// `caller` is the source location of the insert, known when Bevy's
+/// `track_location` feature is enabled, and is recorded on each violation.
+fn validate_on_insert<T: ExpectComponents>(
+    world: &mut DeferredWorld,
+    entity: Entity,
+    caller: Option<&'static Location<'static>>,
+) {
+    let recovered = recovery::recover_missing::<T>(world, entity);
+
+    let mut violations = check_on_insert::<T>(world, entity);
+    violations.retain(|violation| !recovered.contains(&violation.missing));
+    for violation in &mut violations {
+        violation.caller = caller;
+    }
+    report_all(world, &violations);
+
+    let prior = T::expected_prior();
+    let prior_names = T::expected_prior_names();
+
+    for (type_id, name) in prior.iter().zip(prior_names.iter()) {
+        // Missing components were already reported above
+        if !has_component(world, entity, *type_id) {
+            continue;
+        }
+
+        if !add_order::was_added_before(world, entity, *type_id) {
+            let violation = ExpectationViolation {
+                entity,
+                expecting: std::any::type_name::<T>(),
+                missing: name,
+                message: format!(
+                    "{} expects {} to be added before it, but both were added in the same operation on entity {:?}",
+                    std::any::type_name::<T>(),
+                    name,
+                    entity
+                ),
+                caller,
+                kind: ViolationKind::Other,
+            };
+            report(world, &violation);
+        }
+    }
+}
+
+/// Returns every one of `T`'s expectations that `entity` fails.
+///
+/// Add-order expectations depend on insertion timing and are checked by the hook only.
+pub(crate) fn check_expectations<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let mut violations = check_on_insert::<T>(world, entity);
+    violations.extend(check_deferred::<T>(world, entity));
+    violations
+}
+
+/// Returns true if `T` has expectations that can only be judged once the frame
+/// settles, such as those involving entities added after `T`.
+pub(crate) fn has_deferred<T: ExpectComponents>() -> bool {
+    !T::expected_incoming().is_empty()
+        || !T::expected_on_all_children().is_empty()
+        || !T::expected_child_counts().is_empty()
+}
+
+/// Returns every one of `T`'s per-frame expectations that `entity` fails.
+pub(crate) fn check_deferred<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let mut violations = related::check_incoming::<T>(world, entity);
+    violations.extend(children::check_all_children::<T>(world, entity));
+    violations.extend(children::check_child_counts::<T>(world, entity));
+    violations
+}
+
+/// Returns every one of `T`'s expectations that `entity` fails which can be
+/// judged at insertion time.
+fn check_on_insert<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    computed::learn_names(world, T::computed_components());
+
+    let include_required = T::include_required_components();
+    let location = format!("on entity {entity:?}");
+    let mut violations = check_expectations_with::<T>(entity, &location, |type_id| {
+        has_component(world, entity, type_id)
+            || (include_required && is_required_by::<T>(world, type_id))
+    });
+    violations.extend(check_resources::<T>(world, entity));
+    violations.extend(children::check_parent::<T>(world, entity));
+    violations.extend(non_default::check_non_default::<T>(world, entity));
+    violations.extend(predicate::check_predicates::<T>(world, entity));
+    violations.extend(variant::check_variants::<T>(world, entity));
+    violations.extend(related::check_related::<T>(world, entity));
+    violations
+}
+
+/// Returns a violation for each resource `T` expects that is not in the world.
+fn check_resources<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expecting = std::any::type_name::<T>();
+
+    T::expected_resources()
+        .iter()
+        .zip(T::expected_resource_names().iter())
+        .filter(|(type_id, _)| {
+            !world
+                .components()
+                .get_resource_id(**type_id)
+                .is_some_and(|id| world.contains_resource_by_id(id))
+        })
+        .map(|(_, name)| ExpectationViolation {
+            entity,
+            expecting,
+            missing: name,
+            message: format!("{expecting} expects resource {name} but it is not present"),
+            kind: ViolationKind::Other,
+            caller: None,
+        })
+        .collect()
+}
+
+/// Returns every one of `T`'s expectations that `entity` fails, using `has` to
+/// test whether the entity has a component. `location` completes messages such
+/// as "but it was not found on entity 1v0".
+///
+/// Relationship expectations need access to other entities and are not checked here.
+fn check_expectations_with<T: ExpectComponents>(
+    entity: Entity,
+    location: &str,
+    has: impl Fn(TypeId) -> bool,
+) -> Vec<ExpectationViolation> {
+    let mut violations = Vec::new();
+    let expecting = std::any::type_name::<T>();
+
+    let expected = T::expected_components();
+    let names = T::expected_component_names();
+
+    for (type_id, name) in expected.iter().zip(names.iter()) {
+        if !has(*type_id) {
+            let note = T::expected_messages()
+                .iter()
+                .find(|(component, _)| component == name)
+                .map(|(_, template)| {
+                    template
+                        .replace("{expecting}", expecting)
+                        .replace("{missing}", name)
+                        .replace("{entity}", &format!("{entity:?}"))
+                });
+            let mut message = format!("{expecting} expects {name} but it was not found {location}");
+            if let Some(note) = &note {
+                message = format!("{message}: {note}");
+            }
+
+            violations.push(ExpectationViolation {
+                entity,
+                expecting,
+                missing: name,
+                message,
+                kind: ViolationKind::Missing {
+                    location: location.to_owned(),
+                    note,
+                },
+                caller: None,
+            });
+        }
+    }
+
+    let unless_present = T::expected_unless_present();
+    let unless_names = T::expected_unless_present_names();
+
+    for ((absent, required), (absent_name, required_name)) in
+        unless_present.iter().zip(unless_names.iter())
+    {
+        if has(*absent) || has(*required) {
+            continue;
+        }
+
+        violations.push(ExpectationViolation {
+            entity,
+            expecting,
+            missing: required_name,
+            message: format!(
+                "{expecting} expects {required_name} unless {absent_name} is present, but it was not found {location}"
+            ),
+            kind: ViolationKind::Other,
+            caller: None,
+        });
+    }
+
+    for ((guard, required), (guard_name, required_name)) in
+        T::expected_if().iter().zip(T::expected_if_names().iter())
+    {
+        if !has(*guard) || has(*required) {
+            continue;
+        }
+
+        violations.push(ExpectationViolation {
+            entity,
+            expecting,
+            missing: required_name,
+            message: format!(
+                "{expecting} expects {required_name} when {guard_name} is present, but it was not found {location}"
+            ),
+            kind: ViolationKind::Other,
+            caller: None,
+        });
+    }
+
+    violations.extend(check_groups::<T>(entity, location, &has));
+
+    let forbidden = T::forbidden_components();
+    let forbidden_names = T::forbidden_component_names();
+
+    for (type_id, name) in forbidden.iter().zip(forbidden_names.iter()) {
+        if has(*type_id) {
+            violations.push(ExpectationViolation {
+                entity,
+                expecting,
+                missing: name,
+                message: format!("{expecting} forbids {name} but it was found {location}"),
+                kind: ViolationKind::Other,
+                caller: None,
+            });
+        }
+    }
+
+    violations
+}
+
+/// Returns every one of `T`'s expectations on how many of a group of components
+/// are present that `entity` fails.
+fn check_groups<T: ExpectComponents>(
+    entity: Entity,
+    location: &str,
+    has: &impl Fn(TypeId) -> bool,
+) -> Vec<ExpectationViolation> {
+    let mut violations = Vec::new();
+    let expecting = std::any::type_name::<T>();
+
+    for (group, names) in T::expected_any().iter().zip(T::expected_any_names().iter()) {
+        if group.iter().any(|type_id| has(*type_id)) {
+            continue;
+        }
+
+        violations.push(ExpectationViolation {
+            entity,
+            expecting,
+            missing: names.first().copied().unwrap_or_default(),
+            message: format!(
+                "{expecting} expects at least one of {} {location}",
+                names.join(", ")
+            ),
+            kind: ViolationKind::Other,
+            caller: None,
+        });
+    }
+
+    for (group, names) in T::expected_one_of()
+        .iter()
+        .zip(T::expected_one_of_names().iter())
+    {
+        let present: Vec<&str> = group
+            .iter()
+            .zip(names.iter())
+            .filter(|(type_id, _)| has(**type_id))
+            .map(|(_, name)| *name)
+            .collect();
+        if present.len() == 1 {
+            continue;
+        }
+
+        let found = if present.is_empty() {
+            "none".to_owned()
+        } else {
+            format!("{} ({})", present.len(), present.join(", "))
+        };
+        violations.push(ExpectationViolation {
+            entity,
+            expecting,
+            missing: names.first().copied().unwrap_or_default(),
+            message: format!(
+                "{expecting} expects exactly one of {} but found {found} {location}",
+                names.join(", ")
+            ),
+            kind: ViolationKind::Other,
+            caller: None,
+        });
+    }
+
+    violations
+}
+
+/// Returns every one of `T`'s expectations that a bundle of `components` fails,
+/// or nothing if the bundle does not contain `T`.
+///
+/// Only expectations on the presence of components can be judged before spawning.
+pub(crate) fn check_bundle_for<T: ExpectComponents>(
+    components: &[TypeId],
+) -> Vec<ExpectationViolation> {
+    if !components.contains(&TypeId::of::<T>()) {
+        return Vec::new();
+    }
+
+    check_expectations_with::<T>(Entity::PLACEHOLDER, "in the bundle", |type_id| {
+        components.contains(&type_id)
+    })
+}
+
+/// Returns every violation of any installed type's expectations on `entity`.
+pub(crate) fn entity_violations(world: &World, entity: Entity) -> Vec<ExpectationViolation> {
+    lookup::installed(world)
+        .flat_map(|registration| registration.check_entity(world, entity))
+        .collect()
+}
+
+/// Validates `entity` against every registered expectation, returning the
+/// violations instead of reporting them.
+///
+/// Use this to check entities built outside of the hooks' view, such as through
+/// a custom command buffer, with your own error handling. Save systems can call
+/// it before serializing an entity, so a malformed one is not written to disk,
+/// and after a loaded entity is fully restored, to catch corrupt or outdated
+/// saves whose components were inserted one at a time. Works with or
+/// without the plugin: with it, the types it installed are checked, including
+/// those from an [`ExpectationRegistry`](crate::ExpectationRegistry), and
+/// without it every type collected by `inventory` or submitted through
+/// [`register_all_expectations!`](crate::register_all_expectations).
+///
+/// ```rust,ignore
+/// if let Err(violations) = validate_entity(world, entity) {
+///     for violation in &violations {
+///         error!("{} is missing {}", violation.expecting, violation.missing);
+///     }
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns every violation if the entity fails any expectation.
+pub fn validate_entity(world: &World, entity: Entity) -> Result<(), Vec<ExpectationViolation>> {
+    let violations = entity_violations(world, entity);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Validates `entity` in `world` against an explicit list of registrations.
+///
+/// Nothing global is consulted: neither the `inventory` registry nor any
+/// configuration resources. This suits tools that analyze several worlds, or
+/// sandboxed and headless analysis that must only enforce a chosen subset of
+/// expectations. Violations are returned rather than reported.
+///
+/// ```rust,ignore
+/// let physics = ExpectRegistration::of::<PhysicsBody>();
+/// let violations = validate_entity_in(&sandbox, entity, &[&physics]);
+/// ```
+#[must_use]
+pub fn validate_entity_in(
+    world: &World,
+    entity: Entity,
+    types: &[&ExpectRegistration],
+) -> Vec<ExpectationViolation> {
+    types
+        .iter()
+        .flat_map(|registration| registration.check_entity(world, entity))
+        .collect()
+}
+
+/// Returns every one of `T`'s presence expectations that `entity` fails.
+pub(crate) fn check_expectations_on<T: ExpectComponents>(
+    entity: &EntityRef,
+) -> Vec<ExpectationViolation> {
+    let location = format!("on entity {:?}", entity.id());
+    check_expectations_with::<T>(entity.id(), &location, |type_id| {
+        entity.contains_type_id(type_id)
+    })
+}
+
+/// Panics if `entity` fails any of `T`'s expectations.
+///
+/// Works without the plugin, so it can be called at any point where an entity is
+/// suspected to be malformed. Relationship expectations are not checked, since
+/// they need access to other entities. The derive generates a
+/// `debug_assert_<type>_expectations` wrapper that calls this in debug builds only.
+///
+/// # Panics
+///
+/// Panics with every failed expectation if the entity does not satisfy `T`.
+pub fn assert_entity_expectations<T: ExpectComponents>(entity: &EntityRef) {
+    installed::warn_if_plugin_missing(std::any::type_name::<T>());
+
+    let messages: Vec<String> = check_expectations_on::<T>(entity)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    assert!(messages.is_empty(), "{}", messages.join("\n"));
+}
+
+/// Reports every violation, combining consecutive presence failures of one
+/// expecting component on one entity into a single failure that lists every
+/// missing component.
+///
+/// Only failures that share a failure mode are combined, so a per-expectation
+/// mode still applies to its own component.
+pub(crate) fn report_all(world: &mut DeferredWorld, violations: &[ExpectationViolation]) {
+    let mut groups: Vec<(&ExpectationViolation, Vec<&'static str>)> = Vec::new();
+
+    for violation in violations
+        .iter()
+        .filter(|violation| tags::is_enforced(world, violation))
+    {
+        match groups.last_mut() {
+            Some((first, missing)) if can_combine(world, first, violation) => {
+                missing.push(violation.missing);
+            }
+            _ => groups.push((violation, vec![violation.missing])),
+        }
+    }
+
+    for (first, missing) in groups {
+        let ViolationKind::Missing { location, .. } = &first.kind else {
+            report(world, first);
+            continue;
+        };
+
+        let mut message = if missing.len() == 1 {
+            first.message.clone()
+        } else {
+            format!(
+                "{} expects {} but they were not found {location}",
+                first.expecting,
+                missing.join(", ")
+            )
+        };
+        if let Some(present) = verbose::present_components(world, first.entity, first.expecting) {
+            message.push('\n');
+            message.push_str(&present);
+        }
+
+        report(
+            world,
+            &ExpectationViolation {
+                message,
+                ..first.clone()
+            },
+        );
+    }
+}
+
+/// Returns true if `next` can be reported in the same message as `first`.
+///
+/// Presence failures with their own `message` are reported separately, so the
+/// note stays next to the component it is about.
+fn can_combine(world: &World, first: &ExpectationViolation, next: &ExpectationViolation) -> bool {
+    first.entity == next.entity
+        && first.expecting == next.expecting
+        && combinable_location(first)
+            .is_some_and(|location| combinable_location(next) == Some(location))
+        && expectation_mode(world, first) == expectation_mode(world, next)
+}
+
+/// Returns where a presence failure without its own `message` looked for the
+/// missing component, or `None` for any other failure.
+fn combinable_location(violation: &ExpectationViolation) -> Option<&str> {
+    match &violation.kind {
+        ViolationKind::Missing {
+            location,
+            note: None,
+        } => Some(location),
+        _ => None,
+    }
+}
+
+/// Reports a failed expectation according to its own failure mode, if it
+/// declares one, or else the configured [`FailureMode`].
+pub(crate) fn report(world: &mut DeferredWorld, violation: &ExpectationViolation) {
+    if !tags::is_enforced(world, violation) {
+        return;
+    }
+
+    #[cfg(feature = "diagnostics")]
+    crate::diagnostics::count_violation(world);
+    verbose::log_entity_dump(world, violation);
+    verbose::log_backtrace(world);
+
+    if let Some(handler) = world.get_resource::<ViolationHandler>() {
+        handler.handle(violation);
+        return;
+    }
+
+    let mode = expectation_mode(world, violation).unwrap_or_else(|| current_failure_mode(world));
+    match mode {
+        FailureMode::Panic => fail(world, violation),
+        FailureMode::Warn => {
+            if rate_limit::allow(world) {
+                log::warn!("{violation}");
+            }
+        }
+        FailureMode::Log => {
+            if rate_limit::allow(world) {
+                log::error!("{violation}");
+            }
+        }
+        FailureMode::Silent => {}
+        FailureMode::File(_) => file_sink::buffer(world, &violation.to_string()),
+        FailureMode::WarnThenPanic => {
+            if has_warned(world, violation) {
+                fail(world, violation);
+            } else {
+                warn_first(world, violation);
+            }
+        }
+        FailureMode::WarnOnce => {
+            if !has_warned(world, violation) {
+                warn_first(world, violation);
+            }
+        }
+        FailureMode::RemoveComponent => remove_expecting(world, violation),
+        FailureMode::Collect => match world.get_resource_mut::<ValidationReport>() {
+            Some(mut report) => report.violations.push(violation.clone()),
+            None => log::warn!("{violation}"),
+        },
+    }
+}
+
+/// Returns true if the violated pattern has already warned.
+fn has_warned(world: &DeferredWorld, violation: &ExpectationViolation) -> bool {
+    world
+        .get_resource::<WarnedPatterns>()
+        .is_some_and(|warned| warned.contains(violation.expecting, violation.missing))
+}
+
+/// Warns about a pattern for the first time, marking it as warned unless the
+/// warning was rate limited.
+fn warn_first(world: &mut DeferredWorld, violation: &ExpectationViolation) {
+    if !rate_limit::allow(world) {
+        return;
+    }
+
+    if let Some(mut warned) = world.get_resource_mut::<WarnedPatterns>() {
+        warned.first_occurrence(violation.expecting, violation.missing);
+    }
+    log::warn!("{violation}");
+}
+
+/// Queues removal of the expecting component from the violating entity.
+///
+/// Violations not tied to a live entity, such as bundle checks, are only logged.
+fn remove_expecting(world: &mut DeferredWorld, violation: &ExpectationViolation) {
+    let id = lookup::installed_named(world, violation.expecting)
+        .and_then(|registration| world.components().get_id(registration.type_id()));
+    let entity = violation.entity;
+
+    let Some(id) = id.filter(|_| world.get_entity(entity).is_ok()) else {
+        log::warn!("{violation}");
+        return;
+    };
+
+    log::warn!(
+        "{violation}; removing {} from entity {entity:?}",
+        violation.expecting
+    );
+    world.commands().queue(move |world: &mut World| {
+        if let Ok(mut entity) = world.get_entity_mut(entity) {
+            entity.remove_by_id(id);
+        }
+    });
+}
+
+/// Returns the failure mode declared on the violated expectation, if any.
+fn expectation_mode(world: &World, violation: &ExpectationViolation) -> Option<FailureMode> {
+    lookup::installed_named(world, violation.expecting)?
+        .expected_modes()
+        .iter()
+        .find(|(name, _)| *name == violation.missing)
+        .map(|(_, mode)| mode.clone())
+}
+
+/// Points users at their own code from a panic raised inside a component hook.
+///
+/// Hooks are called through function pointers, so `#[track_caller]` cannot carry
+/// the insert's location into the panic, and the reported location is always in
+/// this crate. With `track_location`, the message already names the insert.
+#[cfg(not(feature = "track_location"))]
+const PANIC_HINT: &str = "\nhelp: expectations are checked in component hooks, so the panic location is not your code; \
+     enable the `track_location` feature to name the insert, or set RUST_BACKTRACE=1 and look for the first frame in your crate";
+#[cfg(feature = "track_location")]
+const PANIC_HINT: &str = "";
+
+/// Brings the app down according to the current [`PanicBehavior`].
+fn fail(world: &DeferredWorld, violation: &ExpectationViolation) {
+    match current_panic_behavior(world) {
+        PanicBehavior::Unwind => panic!("{violation}{PANIC_HINT}"),
+        behavior => behavior.fail(&violation.to_string()),
+    }
+}
+
+/// Returns true if the entity has the component with the given `TypeId`.
+///
+/// Table components are checked against the entity's archetype. Sparse-set
+/// components are looked up directly in their set, which skips resolving the
+/// entity's location.
+pub(crate) fn has_component(world: &World, entity: Entity, type_id: TypeId) -> bool {
+    let Some((id, storage)) = component_ids::resolve(world, type_id) else {
+        return false;
+    };
+
+    match storage {
+        StorageType::SparseSet => world
+            .storages()
+            .sparse_sets
+            .get(id)
+            .is_some_and(|set| set.contains(entity)),
+        StorageType::Table => world.entity(entity).contains_id(id),
+    }
+}
+
+/// Returns true if `T` lists the component with the given `TypeId` in its
+/// required-components closure, including components required transitively.
+pub(crate) fn is_required_by<T: ExpectComponents>(world: &World, type_id: TypeId) -> bool {
+    let components = world.components();
+    let (Some(expecting), Some(required)) = (
+        components.get_id(TypeId::of::<T>()),
+        components.get_id(type_id),
+    ) else {
+        return false;
+    };
+
+    components.get_info(expecting).is_some_and(|info| {
+        info.required_components()
+            .iter_ids()
+            .any(|id| id == required)
+    })
+}
@@ -0,0 +1,48 @@
+//! Finding every invalid entity from inside a system.
+
+use bevy_ecs::system::SystemParam;
+use bevy_ecs::world::World;
+
+use crate::{scheduled, ExpectationViolation};
+
+/// System parameter listing every entity that currently fails its expectations.
+///
+/// Unlike the insertion hooks, this sees violations introduced after insertion,
+/// such as by removals. Nothing is reported; the violations are returned for
+/// display, for example on a diagnostics screen refreshed each frame.
+///
+/// # Performance
+///
+/// Each call to [`Self::violations`] scans every entity with a registered
+/// expecting component and checks all of its expectations, costing time
+/// proportional to the number of such entities. The parameter also reads the
+/// whole world, so the system cannot access anything mutably and runs alone.
+/// Prefer running it on demand or on a timer over every frame in large worlds.
+///
+/// ```rust,ignore
+/// fn diagnostics(validator: ExpectationValidator) {
+///     for violation in validator.violations() {
+///         info!("{violation}");
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct ExpectationValidator<'w> {
+    world: &'w World,
+}
+
+impl ExpectationValidator<'_> {
+    /// Returns every violation of every validated type's expectations across
+    /// the world.
+    pub fn violations(&self) -> impl Iterator<Item = ExpectationViolation> + '_ {
+        let world = self.world;
+        scheduled::validated_registrations(world)
+            .into_iter()
+            .flat_map(move |registration| {
+                registration
+                    .entities(world)
+                    .into_iter()
+                    .flat_map(move |entity| registration.check_entity(world, entity))
+            })
+    }
+}
@@ -0,0 +1,54 @@
+//! Expectations that apply only while an enum component is a given variant.
+
+use std::any::TypeId;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::world::World;
+
+use crate::{has_component, ExpectComponents, ExpectationViolation, ViolationKind};
+
+/// An expectation on an enum component that applies only to one variant.
+///
+/// Generated by `#[expects(Target)]` on the `Chasing` variant of an enum
+/// deriving `ExpectComponents`.
+pub struct VariantExpectation {
+    /// Name of the variant the expectation applies to.
+    pub variant: &'static str,
+    /// `TypeId` of the expected component.
+    pub component: TypeId,
+    /// Name of the expected component for error messages.
+    pub component_name: &'static str,
+    /// Returns true if the entity's component is currently this variant.
+    pub is_active: fn(&World, Entity) -> bool,
+}
+
+/// Returns every expectation of the variant `entity`'s `T` currently is that
+/// the entity fails.
+pub(crate) fn check_variants<T: ExpectComponents>(
+    world: &World,
+    entity: Entity,
+) -> Vec<ExpectationViolation> {
+    let expecting = std::any::type_name::<T>();
+
+    T::expected_by_variant()
+        .iter()
+        .filter(|expectation| {
+            (expectation.is_active)(world, entity)
+                && !has_component(world, entity, expectation.component)
+        })
+        .map(|expectation| {
+            let component = expectation.component_name;
+            ExpectationViolation {
+                entity,
+                expecting,
+                missing: component,
+                message: format!(
+                    "{expecting}::{} expects {component} but it was not found on entity {entity:?}",
+                    expectation.variant
+                ),
+                kind: ViolationKind::Other,
+                caller: None,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,131 @@
+//! Dumping a failing entity's components for deep debugging.
+
+use std::backtrace::Backtrace;
+use std::fmt::Write;
+
+use bevy_ecs::component::ComponentInfo;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::name::Name;
+use bevy_ecs::resource::Resource;
+use bevy_ecs::world::World;
+
+use crate::ExpectationViolation;
+
+/// Logs every component of a failing entity alongside each failure, with its
+/// [`Name`] if it has one and the number of entities sharing its archetype.
+///
+/// Insert this resource to enable the dump. It walks the entity's archetype on
+/// every failure, so leave it off outside of debugging sessions.
+///
+/// Component names come from Bevy's `debug` feature, which `bevy` enables by
+/// default. With this crate's `reflect` feature, components registered in the
+/// `AppTypeRegistry` with `#[reflect(Component)]` are also printed with their
+/// debug values.
+///
+/// ```rust,ignore
+/// app.insert_resource(VerboseFailures);
+/// ```
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct VerboseFailures;
+
+/// Logs a backtrace alongside each failure.
+///
+/// Expectations are checked in component hooks, so a panic points into this
+/// crate. The backtrace shows how the insert was reached instead. Capturing one
+/// is slow, so this suits development builds.
+///
+/// ```rust,ignore
+/// app.insert_resource(FailureBacktraces);
+/// ```
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct FailureBacktraces;
+
+/// Logs the components of the violating entity if [`VerboseFailures`] is present.
+pub(crate) fn log_entity_dump(world: &World, violation: &ExpectationViolation) {
+    if !world.contains_resource::<VerboseFailures>() {
+        return;
+    }
+
+    if let Some(dump) = entity_dump(world, violation.entity) {
+        log::warn!("{dump}");
+    }
+}
+
+/// Logs a backtrace of the current failure if [`FailureBacktraces`] is present.
+pub(crate) fn log_backtrace(world: &World) {
+    if world.contains_resource::<FailureBacktraces>() {
+        log::warn!(
+            "backtrace of the failed expectation:\n{}",
+            Backtrace::force_capture()
+        );
+    }
+}
+
+/// Describes each component on `entity`, one per line, or `None` if it does not exist.
+///
+/// The header names the entity if it has a [`Name`], breaks it down into its
+/// index and generation and counts the entities sharing its archetype, which
+/// gives a sense of scale when one entity in a large batch is malformed.
+fn entity_dump(world: &World, entity: Entity) -> Option<String> {
+    let archetype_len = world.get_entity(entity).ok()?.archetype().len();
+    let components = world.inspect_entity(entity).ok()?;
+
+    let named = world
+        .get::<Name>(entity)
+        .map(|name| format!(" \"{name}\""))
+        .unwrap_or_default();
+
+    let mut dump = format!(
+        "components of entity{named} {entity:?} (index {}, generation {}, one of {archetype_len} entities in its archetype):",
+        entity.index_u32(),
+        entity.generation().to_bits()
+    );
+    for info in components {
+        let _ = write!(dump, "\n    {}", info.name());
+        if let Some(value) = reflected_value(world, entity, info) {
+            let _ = write!(dump, ": {value}");
+        }
+    }
+    Some(dump)
+}
+
+/// Returns a line listing the components on `entity`, such as
+/// `entity 42v3 currently has: A, B`.
+///
+/// Best effort: returns `None` if the entity does not exist, or if component
+/// names can't be resolved because Bevy's `debug` feature is off, which is
+/// detected by the expecting component's name not matching `expecting`.
+pub(crate) fn present_components(world: &World, entity: Entity, expecting: &str) -> Option<String> {
+    let names: Vec<String> = world
+        .inspect_entity(entity)
+        .ok()?
+        .map(|info| info.name().to_string())
+        .collect();
+
+    if !names.iter().any(|name| name == expecting) {
+        return None;
+    }
+
+    Some(format!(
+        "entity {entity:?} currently has: {}",
+        names.join(", ")
+    ))
+}
+
+/// Formats the component's value through reflection, if it is registered for it.
+#[cfg(feature = "reflect")]
+fn reflected_value(world: &World, entity: Entity, info: &ComponentInfo) -> Option<String> {
+    use bevy_ecs::reflect::{AppTypeRegistry, ReflectComponent};
+
+    let registry = world.get_resource::<AppTypeRegistry>()?.read();
+    let reflect = registry
+        .get(info.type_id()?)?
+        .data::<ReflectComponent>()?
+        .reflect(world.entity(entity))?;
+    Some(format!("{reflect:?}"))
+}
+
+#[cfg(not(feature = "reflect"))]
+fn reflected_value(_world: &World, _entity: Entity, _info: &ComponentInfo) -> Option<String> {
+    None
+}
@@ -0,0 +1,161 @@
+//! Structured description of a failed expectation.
+
+use std::fmt::{self, Write};
+use std::panic::Location;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::resource::Resource;
+
+/// A single failed expectation on an entity.
+///
+/// The [`Display`](fmt::Display) output is the same message the plugin panics
+/// or logs with: the message, followed by the insert location on its own line
+/// when it is known.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpectationViolation {
+    /// The entity that failed the expectation.
+    pub entity: Entity,
+    /// Type name of the component whose expectation failed.
+    pub expecting: &'static str,
+    /// Name of the component the expectation is about.
+    pub missing: &'static str,
+    /// Human-readable description of the failure.
+    pub message: String,
+    /// What kind of expectation failed, for grouping failures without parsing
+    /// their messages.
+    pub kind: ViolationKind,
+    /// Source location of the insert that caused the failure.
+    ///
+    /// Known for failures raised by hooks when Bevy's `track_location` feature
+    /// is enabled, through this crate's feature of the same name.
+    pub caller: Option<&'static Location<'static>>,
+}
+
+/// The kind of expectation an [`ExpectationViolation`] failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// An expected component was not found.
+    Missing {
+        /// Where the component was looked for, such as `on entity 1v0` or
+        /// `in the bundle`.
+        location: String,
+        /// The expectation's `message`, with its placeholders substituted.
+        note: Option<String>,
+    },
+    /// Any other failed expectation, described by the violation's message.
+    Other,
+}
+
+impl fmt::Display for ExpectationViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)?;
+        if let Some(caller) = self.caller {
+            write!(f, "\ninserted at {caller}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ExpectationViolation {}
+
+/// Every expectation a bundle failed, returned by
+/// [`ExpectSpawnExt::spawn_checked`](crate::ExpectSpawnExt::spawn_checked).
+///
+/// The [`Display`](fmt::Display) output lists one violation per line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpectationReport {
+    /// The failed expectations, in registration order.
+    pub violations: Vec<ExpectationViolation>,
+}
+
+impl fmt::Display for ExpectationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, violation) in self.violations.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ExpectationReport {}
+
+/// Every failure reported under [`FailureMode::Collect`](crate::FailureMode::Collect).
+///
+/// The plugin inserts this resource. Read it after spawning to assert on the
+/// complete set of failures:
+///
+/// ```rust,ignore
+/// app.insert_resource(FailureMode::Collect);
+/// // spawn entities...
+/// let report = app.world().resource::<ValidationReport>();
+/// assert_eq!(report.violations.len(), 2);
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// The collected failures, in the order they were reported.
+    pub violations: Vec<ExpectationViolation>,
+}
+
+impl ValidationReport {
+    /// Removes every collected failure, for example between test phases.
+    pub fn clear(&mut self) {
+        self.violations.clear();
+    }
+
+    /// Returns true if no failures have been collected.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Serializes the report as a JSON object with a `violations` array, one
+    /// object per failure with its `entity`, `expecting`, `missing`, `message`
+    /// and `caller`, which is `null` when the insert location is unknown.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"violations\":[");
+        for (index, violation) in self.violations.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"entity\":");
+            push_json_string(&mut json, &violation.entity.to_string());
+            json.push_str(",\"expecting\":");
+            push_json_string(&mut json, violation.expecting);
+            json.push_str(",\"missing\":");
+            push_json_string(&mut json, violation.missing);
+            json.push_str(",\"message\":");
+            push_json_string(&mut json, &violation.message);
+            json.push_str(",\"caller\":");
+            match violation.caller {
+                Some(caller) => push_json_string(&mut json, &caller.to_string()),
+                None => json.push_str("null"),
+            }
+            json.push('}');
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+/// Appends `value` to `json` as a quoted, escaped JSON string.
+fn push_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            ch if ch.is_control() => {
+                let _ = write!(json, "\\u{:04x}", u32::from(ch));
+            }
+            ch => json.push(ch),
+        }
+    }
+    json.push('"');
+}
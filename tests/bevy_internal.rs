@@ -0,0 +1,44 @@
+//! Types in this test crate have paths starting with `bevy_internal::`, the name
+//! of one of Bevy's own crates, so the internal filter treats them as Bevy's.
+#![cfg(feature = "validate")]
+
+use bevy_app::{App, Update};
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct InternalBody;
+
+#[test]
+fn skip_bevy_internal_skips_internal_types() {
+    let mut app = App::new();
+    app.add_plugins(
+        ExpectedComponentsPlugin
+            .skip_bevy_internal()
+            .validate_after_flush(Update),
+    );
+
+    app.world_mut().spawn(InternalBody);
+    app.update();
+}
+
+#[test]
+fn skip_bevy_internal_skips_internal_types_in_hooks() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.skip_bevy_internal());
+
+    app.world_mut().spawn(InternalBody);
+}
+
+#[test]
+#[should_panic(expected = "InternalBody expects bevy_internal::Position")]
+fn internal_types_are_validated_by_default() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(InternalBody);
+}
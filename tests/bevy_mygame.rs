@@ -0,0 +1,38 @@
+//! Types in this test crate have paths starting with `bevy_mygame::`, like a
+//! third-party or user crate named after Bevy, which the internal filter must
+//! not mistake for one of Bevy's own crates.
+#![cfg(feature = "validate")]
+
+use bevy_app::{App, Update};
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct Body;
+
+#[test]
+#[should_panic(expected = "bevy_mygame::Body expects bevy_mygame::Position")]
+fn skip_bevy_internal_validates_bevy_named_crates() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.skip_bevy_internal());
+
+    app.world_mut().spawn(Body);
+}
+
+#[test]
+#[should_panic(expected = "bevy_mygame::Body expects bevy_mygame::Position")]
+fn skip_bevy_internal_validates_bevy_named_crates_when_scheduled() {
+    let mut app = App::new();
+    app.add_plugins(
+        ExpectedComponentsPlugin
+            .skip_bevy_internal()
+            .validate_after_flush(Update),
+    );
+
+    app.world_mut().spawn(Body);
+    app.update();
+}
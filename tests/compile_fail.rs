@@ -0,0 +1,7 @@
+//! Derive inputs the macro must reject, checked against the expected compiler
+//! errors in `tests/ui`.
+
+#[test]
+fn derive_rejects_invalid_expectations() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
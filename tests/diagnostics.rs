@@ -0,0 +1,38 @@
+//! Needs the `diagnostics` feature, so it lives in its own test binary.
+#![cfg(feature = "diagnostics")]
+
+use bevy_app::App;
+use bevy_diagnostic::DiagnosticsStore;
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::VIOLATIONS_DIAGNOSTIC;
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct Enemy;
+
+fn latest_violations(app: &App) -> Option<f64> {
+    app.world()
+        .resource::<DiagnosticsStore>()
+        .get(&VIOLATIONS_DIAGNOSTIC)?
+        .value()
+}
+
+#[test]
+fn violations_are_measured_per_frame() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(Enemy);
+    app.world_mut().spawn(Enemy);
+    app.world_mut().spawn((Enemy, Position));
+    app.update();
+    assert_eq!(latest_violations(&app), Some(2.0));
+
+    app.update();
+    assert_eq!(latest_violations(&app), Some(0.0));
+}
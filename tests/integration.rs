@@ -1,6 +1,37 @@
-use bevy_app::App;
+//! Needs the `validate` feature, which is on by default.
+#![cfg(feature = "validate")]
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use bevy_app::{App, Last, Update};
+use bevy_ecs::bundle::Bundle;
 use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::hierarchy::ChildOf;
+use bevy_ecs::query::{With, Without};
+use bevy_ecs::resource::Resource;
+use bevy_ecs::schedule::common_conditions::resource_exists;
+use bevy_ecs::schedule::IntoScheduleConfigs;
+use bevy_ecs::system::{Commands, Query, RunSystemOnce};
+use bevy_ecs::world::World;
 use bevy_expected_components::prelude::*;
+use bevy_expected_components::{
+    all_registrations, current_failure_mode, expect_current, expectation_graph,
+    expectation_stability, is_registered, never_spawned_types, register_asset_expectation,
+    register_module_expectations, register_named_expectation, register_recovery,
+    register_single_expectation, registry_snapshot, registry_to_dot, validate_bundle,
+    validate_entity, validate_entity_in, validate_scene_root, validate_scene_world,
+    validation_enabled, validation_stats_by_type, validation_system, CompletionDeadline,
+    CurrentEntity, DeclaredComponents, EnabledTags, ErrorBudget, ExpectRegistration,
+    ExpectSpawnExt, ExpectationGroups, ExpectationLookup, ExpectationOverrides,
+    ExpectationRegistry, ExpectationStatus, ExpectationValidator, ExpectationViolation,
+    FailureBacktraces, JsonReport, PanicBehavior, SingletonGracePeriod, SpawnTracker, Stability,
+    ValidationEnabled, ValidationGate, ValidationReport, ValidationStats, ValidationSystems,
+    ValidationTimeBudget, VerboseFailures, ViolationKind, ViolationRateLimit,
+};
 
 #[derive(Component, Default)]
 struct Position;
@@ -51,6 +82,23 @@ fn panic_message_includes_missing_component_name() {
     app.world_mut().spawn((PhysicsBody, Velocity));
 }
 
+#[test]
+#[cfg(not(feature = "track_location"))]
+fn panic_message_points_at_user_code_not_crate_internals() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        app.world_mut().spawn((PhysicsBody, Velocity));
+    }))
+    .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(!message.contains("src/validation.rs"));
+    assert!(message.contains("on entity"));
+    assert!(message.contains("RUST_BACKTRACE=1"));
+}
+
 #[test]
 fn no_validation_without_plugin() {
     let mut app = App::new();
@@ -81,3 +129,3299 @@ fn multiple_expects_attributes() {
 
     app.world_mut().spawn((MultiAttribute, Position, Velocity));
 }
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+#[expect(dead_code)]
+struct Labelled {
+    label: u32,
+}
+
+#[test]
+#[should_panic(expected = "Labelled expects integration::Position but it was not found on entity")]
+fn lint_expect_attribute_coexists_with_expects() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Labelled { label: 1 }, Position));
+    app.world_mut().spawn(Labelled { label: 2 });
+}
+
+mod physics {
+    use bevy_ecs::component::Component;
+
+    #[derive(Component, Default)]
+    pub struct Momentum;
+
+    pub mod nested {
+        use bevy_ecs::component::Component;
+
+        #[derive(Component, Default)]
+        pub struct Inertia;
+    }
+}
+
+use physics::Momentum as AliasedMomentum;
+
+type InertiaAlias = physics::nested::Inertia;
+
+#[derive(Component, ExpectComponents)]
+#[expects(AliasedMomentum, physics::nested::Inertia)]
+struct AliasedExpectations;
+
+#[derive(Component, ExpectComponents)]
+#[expects(self::physics::Momentum, InertiaAlias)]
+struct QualifiedExpectations;
+
+#[test]
+fn aliased_and_nested_paths_resolve_to_the_same_types() {
+    let expected = [
+        TypeId::of::<physics::Momentum>(),
+        TypeId::of::<physics::nested::Inertia>(),
+    ];
+
+    assert_eq!(AliasedExpectations::expected_components(), expected);
+    assert_eq!(QualifiedExpectations::expected_components(), expected);
+}
+
+#[derive(Component, ExpectComponents)]
+#[allow(clippy::duplicated_attributes)] // The repetition is under test
+#[expects(Velocity)]
+#[expects(Velocity, Position)]
+struct RepeatedExpectations;
+
+#[test]
+fn repeated_expectations_are_deduplicated() {
+    assert_eq!(
+        RepeatedExpectations::expected_components(),
+        [TypeId::of::<Velocity>(), TypeId::of::<Position>()]
+    );
+    assert_eq!(RepeatedExpectations::expected_component_names().len(), 2);
+}
+
+#[test]
+fn aliased_paths_are_named_by_their_definition() {
+    let names = [
+        "integration::physics::Momentum",
+        "integration::physics::nested::Inertia",
+    ];
+
+    assert_eq!(AliasedExpectations::expected_component_names(), names);
+    assert_eq!(QualifiedExpectations::expected_component_names(), names);
+}
+
+#[test]
+#[should_panic(
+    expected = "AliasedExpectations expects integration::physics::nested::Inertia but it was not found"
+)]
+fn aliased_paths_are_validated() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut()
+        .spawn((AliasedExpectations, physics::Momentum));
+}
+
+#[derive(Component)]
+struct HandWritten;
+
+impl ExpectComponents for HandWritten {
+    const EXPECTED: &'static [TypeId] = &[TypeId::of::<Position>()];
+    const EXPECTED_NAMES: &'static [&'static str] = &["Position"];
+}
+
+bevy_expected_components::inventory::submit!(ExpectRegistration::of::<HandWritten>());
+
+#[test]
+fn hand_written_impl_succeeds_when_expected_present() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((HandWritten, Position));
+}
+
+#[test]
+#[should_panic(expected = "Position")]
+fn hand_written_impl_panics_when_expected_missing() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((HandWritten,));
+}
+
+#[derive(Component)]
+struct LegacyRegistered;
+
+impl ExpectComponents for LegacyRegistered {
+    const EXPECTED: &'static [TypeId] = &[TypeId::of::<Position>()];
+    const EXPECTED_NAMES: &'static [&'static str] = &["Position"];
+}
+
+#[allow(deprecated)]
+mod legacy_registration {
+    use bevy_expected_components::{register_hooks_for, ExpectRegistration};
+
+    bevy_expected_components::inventory::submit!(ExpectRegistration::new(
+        register_hooks_for::<super::LegacyRegistered>
+    ));
+}
+
+#[test]
+#[should_panic(expected = "LegacyRegistered expects Position")]
+fn deprecated_hooks_only_registration_still_validates() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((LegacyRegistered, Position));
+    app.world_mut().spawn(LegacyRegistered);
+}
+
+#[test]
+fn deprecated_hooks_only_registration_is_not_indexed() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let lookup = app.world().resource::<ExpectationLookup>();
+    assert_eq!(
+        lookup.expected_components_for(TypeId::of::<ExpectRegistration>()),
+        None
+    );
+    assert_eq!(lookup.expected_components_for_name(""), None);
+}
+
+#[derive(Component, Default)]
+struct Static;
+
+#[derive(Component, ExpectComponents)]
+#[expect_unless_present(Static, Velocity)]
+struct Body;
+
+#[test]
+fn unless_present_skips_when_condition_present() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Body, Static));
+}
+
+#[test]
+fn unless_present_succeeds_when_required_present() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Body, Velocity));
+}
+
+#[test]
+#[should_panic(expected = "Velocity unless")]
+fn unless_present_panics_when_condition_and_required_missing() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Body,));
+}
+
+#[derive(Component)]
+struct PhysicsEnabled;
+
+#[derive(Component)]
+struct RigidBody;
+
+#[derive(Component, ExpectComponents)]
+#[expect_if(PhysicsEnabled, RigidBody)]
+struct Ragdoll;
+
+#[test]
+fn expect_if_skips_when_guard_absent() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(Ragdoll);
+}
+
+#[test]
+fn expect_if_succeeds_when_guard_and_required_present() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Ragdoll, PhysicsEnabled, RigidBody));
+}
+
+#[test]
+#[should_panic(
+    expected = "Ragdoll expects integration::RigidBody when integration::PhysicsEnabled is present, but it was not found on entity"
+)]
+fn expect_if_panics_when_guard_present_and_required_missing() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Ragdoll, PhysicsEnabled));
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct TrackedSpawned;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct TrackedNeverSpawned;
+
+#[test]
+fn never_spawned_types_reports_unspawned_expecting_types() {
+    let mut app = App::new();
+    app.init_resource::<SpawnTracker>();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((TrackedSpawned, Position));
+
+    let never_spawned = never_spawned_types(app.world());
+    assert!(never_spawned.contains(&std::any::type_name::<TrackedNeverSpawned>()));
+    assert!(!never_spawned.contains(&std::any::type_name::<TrackedSpawned>()));
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position, require_prior)]
+struct Attachment;
+
+#[test]
+fn require_prior_succeeds_when_expected_added_earlier() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app.world_mut().spawn(Position).id();
+    app.world_mut().entity_mut(entity).insert(Attachment);
+}
+
+#[test]
+#[should_panic(expected = "same operation")]
+fn require_prior_panics_when_added_in_same_operation() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Position, Attachment));
+}
+
+#[test]
+#[should_panic(expected = "same operation")]
+fn require_prior_panics_after_expected_component_readded() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app.world_mut().spawn(Position).id();
+    app.world_mut().entity_mut(entity).remove::<Position>();
+    app.world_mut()
+        .entity_mut(entity)
+        .insert((Position, Attachment));
+}
+
+#[test]
+#[should_panic(
+    expected = "cannot remove integration::Velocity while integration::PhysicsBody is present on entity"
+)]
+fn removing_expected_component_panics() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, Velocity))
+        .id();
+    app.world_mut().entity_mut(entity).remove::<Velocity>();
+}
+
+#[test]
+#[should_panic(expected = "cannot remove integration::Velocity")]
+fn removing_component_registered_before_plugin_panics() {
+    let mut app = App::new();
+    app.world_mut().register_component::<Velocity>();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, Velocity))
+        .id();
+    app.world_mut().entity_mut(entity).remove::<Velocity>();
+}
+
+#[test]
+fn removing_component_registered_after_expecting_component_is_reported() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.with_failure_mode(FailureMode::Collect));
+
+    let entity = app.world_mut().spawn((PhysicsBody, Position)).id();
+    app.world_mut().entity_mut(entity).insert(Velocity);
+    app.update();
+    app.world_mut().entity_mut(entity).remove::<Velocity>();
+
+    let violations = &app.world().resource::<ValidationReport>().violations;
+    assert_eq!(violations.len(), 2);
+    assert!(violations[1]
+        .message
+        .starts_with("cannot remove integration::Velocity"));
+}
+
+#[test]
+fn removing_expected_component_with_expecting_component_succeeds() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, Velocity))
+        .id();
+    app.world_mut()
+        .entity_mut(entity)
+        .remove::<(PhysicsBody, Velocity)>();
+    app.world_mut().entity_mut(entity).remove::<Position>();
+}
+
+#[test]
+fn on_insert_validates_reinserted_component() {
+    let mut app = App::new();
+    app.add_plugins(
+        ExpectedComponentsPlugin
+            .on_insert()
+            .with_failure_mode(FailureMode::Collect),
+    );
+
+    let entity = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, Velocity))
+        .id();
+    app.world_mut().entity_mut(entity).remove::<Velocity>();
+    app.world_mut().resource_mut::<ValidationReport>().clear();
+
+    app.world_mut().entity_mut(entity).insert(PhysicsBody);
+
+    let violations = &app.world().resource::<ValidationReport>().violations;
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].entity, entity);
+    assert_eq!(violations[0].missing, "integration::Velocity");
+}
+
+#[test]
+fn despawning_entity_with_expectations_succeeds() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, Velocity))
+        .id();
+    app.world_mut().despawn(entity);
+}
+
+#[test]
+fn current_failure_mode_defaults_to_panic() {
+    let app = App::new();
+
+    assert_eq!(current_failure_mode(app.world()), FailureMode::Panic);
+    assert!(validation_enabled(app.world()));
+}
+
+#[test]
+fn current_failure_mode_reflects_configuration() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Warn);
+    app.insert_resource(ValidationEnabled(false));
+
+    assert_eq!(current_failure_mode(app.world()), FailureMode::Warn);
+    assert!(!validation_enabled(app.world()));
+
+    app.insert_resource(ValidationEnabled(true));
+    assert!(validation_enabled(app.world()));
+}
+
+#[test]
+fn warn_mode_does_not_panic() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Warn);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((PhysicsBody,));
+}
+
+#[test]
+fn disabled_validation_does_not_panic() {
+    let mut app = App::new();
+    app.insert_resource(ValidationEnabled(false));
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((PhysicsBody,));
+}
+
+#[derive(Resource, Default)]
+struct PrefabLibrary(HashMap<u32, DeclaredComponents>);
+
+#[derive(Component)]
+struct PrefabRef(u32);
+
+fn declared_by_prefab(world: &World, prefab: &PrefabRef) -> DeclaredComponents {
+    world.resource::<PrefabLibrary>().0[&prefab.0].clone()
+}
+
+fn prefab_app(declared: DeclaredComponents) -> App {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    app.insert_resource(PrefabLibrary(HashMap::from([(0, declared)])));
+    register_asset_expectation::<PrefabRef, Velocity>(app.world_mut(), declared_by_prefab);
+    app.world_mut().spawn(PrefabRef(0));
+    app
+}
+
+#[test]
+fn asset_expectation_succeeds_when_asset_declares_component() {
+    let mut app = prefab_app(DeclaredComponents::Loaded(vec![TypeId::of::<Velocity>()]));
+
+    app.update();
+}
+
+#[test]
+fn asset_expectation_skips_loading_assets() {
+    let mut app = prefab_app(DeclaredComponents::Loading);
+
+    app.update();
+}
+
+#[test]
+#[should_panic(expected = "expects its asset to declare")]
+fn asset_expectation_panics_when_asset_lacks_component() {
+    let mut app = prefab_app(DeclaredComponents::Loaded(vec![TypeId::of::<Position>()]));
+
+    app.update();
+}
+
+#[test]
+fn asset_expectation_reports_each_failure_once() {
+    let mut app = prefab_app(DeclaredComponents::Loaded(vec![TypeId::of::<Position>()]));
+    app.insert_resource(FailureMode::Collect);
+
+    app.update();
+    app.update();
+    assert_eq!(
+        app.world().resource::<ValidationReport>().violations.len(),
+        1
+    );
+
+    // Fixed and broken again, so reported again
+    app.insert_resource(PrefabLibrary(HashMap::from([(
+        0,
+        DeclaredComponents::Loaded(vec![TypeId::of::<Velocity>()]),
+    )])));
+    app.update();
+    app.insert_resource(PrefabLibrary(HashMap::from([(
+        0,
+        DeclaredComponents::Loaded(Vec::new()),
+    )])));
+    app.update();
+    assert_eq!(
+        app.world().resource::<ValidationReport>().violations.len(),
+        2
+    );
+}
+
+#[test]
+fn file_mode_appends_failures_to_file() {
+    let path = std::env::temp_dir().join(format!(
+        "bevy_expected_components_failures_{}.log",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+
+    let mut app = App::new();
+    app.insert_resource(FailureMode::File(path.clone()));
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((PhysicsBody, Velocity));
+    app.world_mut().spawn((PhysicsBody, Position));
+    app.update();
+    app.world_mut().spawn((SingleExpectation,));
+    app.update();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains("Position"));
+    assert!(lines[1].contains("Velocity"));
+    assert!(lines[2].contains("SingleExpectation"));
+}
+
+fn spawn_body_then_dependencies(mut commands: Commands) {
+    commands.spawn(PhysicsBody).insert((Position, Velocity));
+}
+
+fn spawn_body_without_dependencies(mut commands: Commands) {
+    commands.spawn(PhysicsBody);
+}
+
+#[test]
+fn validate_after_flush_sees_commands_applied_in_order() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.validate_after_flush(Update));
+    app.add_systems(
+        Update,
+        spawn_body_then_dependencies.before(ValidationSystems),
+    );
+
+    app.update();
+}
+
+#[test]
+#[should_panic(expected = "expects")]
+fn validate_after_flush_panics_for_entities_spawned_by_commands() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.validate_after_flush(Update));
+    app.add_systems(
+        Update,
+        spawn_body_without_dependencies.before(ValidationSystems),
+    );
+
+    app.update();
+}
+
+#[test]
+#[should_panic(expected = "expects")]
+fn hook_validation_fails_on_partially_applied_commands() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    app.add_systems(Update, spawn_body_then_dependencies);
+
+    app.update();
+}
+
+#[test]
+fn generated_debug_assert_passes_for_valid_entity() {
+    let mut app = App::new();
+
+    let entity = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, Velocity))
+        .id();
+    debug_assert_physics_body_expectations(&app.world().entity(entity));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Position")]
+fn generated_debug_assert_panics_for_violating_entity() {
+    let mut app = App::new();
+
+    let entity = app.world_mut().spawn((PhysicsBody, Velocity)).id();
+    debug_assert_physics_body_expectations(&app.world().entity(entity));
+}
+
+static CUSTOM_PANIC_MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_custom_panic(message: &str) {
+    CUSTOM_PANIC_MESSAGES
+        .lock()
+        .unwrap()
+        .push(message.to_owned());
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct CustomPanicTarget;
+
+#[test]
+#[should_panic(expected = "Position")]
+fn unwind_panic_behavior_panics() {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Unwind);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((PhysicsBody, Velocity));
+}
+
+#[test]
+fn custom_panic_behavior_calls_handler() {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Custom(record_custom_panic));
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(CustomPanicTarget);
+
+    let messages = CUSTOM_PANIC_MESSAGES.lock().unwrap();
+    assert!(messages
+        .iter()
+        .any(|message| message.contains("CustomPanicTarget")));
+}
+
+#[test]
+fn handler_replaces_failure_mode() {
+    let handled = std::sync::Arc::new(Mutex::new(Vec::new()));
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin::development().with_handler({
+        let handled = handled.clone();
+        move |violation: &ExpectationViolation| {
+            handled.lock().unwrap().push(violation.missing);
+        }
+    }));
+
+    app.world_mut().spawn(CustomPanicTarget);
+
+    assert_eq!(*handled.lock().unwrap(), ["integration::Position"]);
+}
+
+static REPEATED_PANIC_MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_repeated_panic(message: &str) {
+    REPEATED_PANIC_MESSAGES
+        .lock()
+        .unwrap()
+        .push(message.to_owned());
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct RepeatOffender;
+
+#[test]
+fn warn_then_panic_warns_first_then_panics_on_repeat() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::WarnThenPanic);
+    app.insert_resource(PanicBehavior::Custom(record_repeated_panic));
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(RepeatOffender);
+    assert!(REPEATED_PANIC_MESSAGES.lock().unwrap().is_empty());
+
+    app.world_mut().spawn(RepeatOffender);
+    let messages = REPEATED_PANIC_MESSAGES.lock().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("RepeatOffender expects"));
+}
+
+#[test]
+fn warn_then_panic_tracks_patterns_separately() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::WarnThenPanic);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    // Different expecting types are different patterns, so each only warns
+    app.world_mut().spawn(SingleExpectation);
+    app.world_mut().spawn(PhysicsBody);
+}
+
+#[test]
+fn log_mode_continues_after_failure() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Log);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(PhysicsBody);
+    app.world_mut().spawn(SingleExpectation);
+    app.update();
+}
+
+#[test]
+fn silent_mode_ignores_failures_but_still_validates() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Silent);
+    app.init_resource::<ValidationStats>();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(PhysicsBody);
+
+    let stats = validation_stats_by_type(app.world());
+    assert_eq!(stats[std::any::type_name::<PhysicsBody>()].validations, 1);
+}
+
+#[test]
+fn collect_mode_records_failures_in_report() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let body = app.world_mut().spawn(PhysicsBody).id();
+    app.world_mut().spawn(SingleExpectation);
+    app.world_mut().spawn((PhysicsBody, Position, Velocity));
+    app.update();
+
+    let report = app.world().resource::<ValidationReport>();
+    assert_eq!(report.violations.len(), 2);
+    assert_eq!(report.violations[0].entity, body);
+    assert_eq!(
+        report.violations[1].expecting,
+        std::any::type_name::<SingleExpectation>()
+    );
+}
+
+#[test]
+fn expected_components_registered_after_first_check_are_found() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    // Position and Velocity are not registered in the world until this spawn
+    app.world_mut().spawn(PhysicsBody);
+    app.world_mut().spawn((PhysicsBody, Position, Velocity));
+    app.world_mut().spawn((PhysicsBody, Position, Velocity));
+
+    assert_eq!(
+        app.world().resource::<ValidationReport>().violations.len(),
+        1
+    );
+}
+
+#[test]
+fn validation_report_clear_resets_between_phases() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(PhysicsBody);
+    app.world_mut().resource_mut::<ValidationReport>().clear();
+    assert!(app.world().resource::<ValidationReport>().is_empty());
+
+    app.world_mut().spawn(SingleExpectation);
+    assert_eq!(
+        app.world().resource::<ValidationReport>().violations.len(),
+        1
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "PhysicsBody expects integration::Position, integration::Velocity but they were not found on entity"
+)]
+fn all_missing_components_reported_in_one_message() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(PhysicsBody);
+}
+
+#[test]
+#[should_panic(
+    expected = "PhysicsBody expects integration::Position, integration::Velocity but they were not found on entity"
+)]
+fn all_missing_components_reported_in_one_message_when_scheduled() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.validate_after_flush(Last));
+
+    app.world_mut().spawn(PhysicsBody);
+    app.update();
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position, mode = "panic")]
+#[expect_warn(Velocity)]
+struct MixedModes;
+
+#[test]
+fn per_expectation_warn_overrides_global_panic() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((MixedModes, Position));
+}
+
+#[test]
+#[should_panic(expected = "MixedModes expects integration::Position")]
+fn per_expectation_panic_overrides_global_warn() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Warn);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((MixedModes, Velocity));
+}
+
+#[test]
+#[should_panic(expected = "MixedModes expects integration::Position")]
+fn mixed_modes_panic_only_for_critical_expectation() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(MixedModes);
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(
+    Position,
+    message = "did you forget to call spawn_enemy() for {entity}?"
+)]
+#[expects(Velocity)]
+struct Hinted;
+
+#[test]
+fn custom_message_is_appended_with_substitutions() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app.world_mut().spawn((Hinted, Velocity)).id();
+
+    let report = app.world().resource::<ValidationReport>();
+    assert_eq!(
+        report.violations[0].message.lines().next().unwrap(),
+        format!(
+            "{} expects {} but it was not found on entity {entity:?}: did you forget to call spawn_enemy() for {entity:?}?",
+            std::any::type_name::<Hinted>(),
+            std::any::type_name::<Position>()
+        )
+    );
+}
+
+#[test]
+fn custom_message_failure_is_not_combined_with_others() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app.world_mut().spawn(Hinted).id();
+
+    let violations = &app.world().resource::<ValidationReport>().violations;
+    assert_eq!(violations.len(), 2);
+    assert_eq!(
+        violations[0].kind,
+        ViolationKind::Missing {
+            location: format!("on entity {entity:?}"),
+            note: Some(format!(
+                "did you forget to call spawn_enemy() for {entity:?}?"
+            )),
+        }
+    );
+    assert_eq!(violations[1].missing, std::any::type_name::<Velocity>());
+}
+
+#[test]
+fn violations_describe_their_kind() {
+    let mut world = World::new();
+    let entity = world.spawn(PhysicsBody).id();
+    let bundle = validate_bundle::<(PhysicsBody, Position)>().unwrap_err();
+
+    let violations = validate_entity(&world, entity).unwrap_err();
+    assert_eq!(
+        violations[0].kind,
+        ViolationKind::Missing {
+            location: format!("on entity {entity:?}"),
+            note: None,
+        }
+    );
+    assert_eq!(
+        bundle.violations[0].kind,
+        ViolationKind::Missing {
+            location: "in the bundle".to_owned(),
+            note: None,
+        }
+    );
+
+    let entity = world.spawn((Anchored, Velocity)).id();
+    let violations = validate_entity(&world, entity).unwrap_err();
+    assert!(violations
+        .iter()
+        .all(|violation| violation.kind == ViolationKind::Other));
+}
+
+#[test]
+fn custom_message_applies_only_to_its_expectation() {
+    let mut world = World::new();
+    let entity = world.spawn(Hinted).id();
+
+    let violations = validate_entity(&world, entity).unwrap_err();
+    assert!(violations[0]
+        .message
+        .ends_with(&format!("spawn_enemy() for {entity:?}?")));
+    assert!(violations[1]
+        .message
+        .ends_with(&format!("but it was not found on entity {entity:?}")));
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+#[expects(level = warn, Velocity)]
+#[expects(level = error, Static)]
+struct Leveled;
+
+#[test]
+fn warn_and_error_levels_do_not_panic() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Leveled, Position));
+}
+
+#[test]
+#[should_panic(expected = "Leveled expects integration::Position")]
+fn default_level_panics() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Leveled, Velocity, Static));
+}
+
+#[test]
+fn levels_map_to_failure_modes() {
+    let modes = ExpectRegistration::of::<Leveled>().expected_modes();
+
+    assert_eq!(
+        modes,
+        &[
+            (std::any::type_name::<Velocity>(), FailureMode::Warn),
+            (std::any::type_name::<Static>(), FailureMode::Log),
+        ]
+    );
+}
+
+#[test]
+fn remove_component_mode_strips_expecting_component() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::RemoveComponent);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app.world_mut().spawn((PhysicsBody, Position)).id();
+
+    let entity = app.world().entity(entity);
+    assert!(!entity.contains::<PhysicsBody>());
+    assert!(entity.contains::<Position>());
+}
+
+#[test]
+fn remove_component_mode_keeps_valid_components() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::RemoveComponent);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, Velocity))
+        .id();
+
+    assert!(app.world().entity(entity).contains::<PhysicsBody>());
+}
+
+static MANUAL_FLUSH_MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_manual_flush(message: &str) {
+    MANUAL_FLUSH_MESSAGES
+        .lock()
+        .unwrap()
+        .push(message.to_owned());
+}
+
+#[test]
+fn manual_flush_validates_queued_inserts() {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Custom(record_manual_flush));
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let world = app.world_mut();
+    let valid = world
+        .commands()
+        .spawn((PhysicsBody, Position, Velocity))
+        .id();
+    let broken = world.commands().spawn((PhysicsBody, Position)).id();
+    assert!(MANUAL_FLUSH_MESSAGES.lock().unwrap().is_empty());
+
+    world.flush();
+
+    let messages = MANUAL_FLUSH_MESSAGES.lock().unwrap();
+    assert_eq!(messages.len(), 1);
+    let first_line = messages[0].lines().next().unwrap();
+    assert!(first_line.ends_with(&format!("{broken:?}")));
+    assert!(!first_line.ends_with(&format!("{valid:?}")));
+}
+
+static SYSTEM_FLUSH_MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_system_flush(message: &str) {
+    SYSTEM_FLUSH_MESSAGES
+        .lock()
+        .unwrap()
+        .push(message.to_owned());
+}
+
+#[test]
+fn system_flush_validates_like_manual_flush() {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Custom(record_system_flush));
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let broken = app
+        .world_mut()
+        .run_system_once(|mut commands: Commands| commands.spawn(PhysicsBody).id())
+        .unwrap();
+
+    let messages = SYSTEM_FLUSH_MESSAGES.lock().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0]
+        .lines()
+        .next()
+        .unwrap()
+        .ends_with(&format!("{broken:?}")));
+}
+
+#[derive(Component)]
+#[relationship(relationship_target = LinkedFrom)]
+struct LinkedTo(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = LinkedTo)]
+struct LinkedFrom(Vec<Entity>);
+
+#[derive(Component, Default)]
+struct Synced;
+
+#[derive(Component, ExpectComponents)]
+#[expect_both_ends(LinkedTo, Synced)]
+struct Mirror;
+
+#[test]
+fn both_ends_succeeds_when_source_and_target_have_component() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let target = app.world_mut().spawn(Synced).id();
+    app.world_mut().spawn((Mirror, LinkedTo(target), Synced));
+}
+
+#[test]
+#[should_panic(expected = "not found on target")]
+fn both_ends_panics_when_only_source_has_component() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let target = app.world_mut().spawn_empty().id();
+    app.world_mut().spawn((Mirror, LinkedTo(target), Synced));
+}
+
+#[test]
+#[should_panic(expected = "on both ends of")]
+fn both_ends_panics_when_only_target_has_component() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let target = app.world_mut().spawn(Synced).id();
+    app.world_mut().spawn((Mirror, LinkedTo(target)));
+}
+
+#[test]
+#[should_panic(expected = "relationship but none was found")]
+fn both_ends_panics_without_relationship() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Mirror, Synced));
+}
+
+#[derive(Component)]
+#[relationship(relationship_target = Equipping)]
+struct EquippedBy(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = EquippedBy)]
+struct Equipping(Vec<Entity>);
+
+#[derive(Component)]
+struct Hand;
+
+#[derive(Component, ExpectComponents)]
+#[expect_on_related(EquippedBy, Hand)]
+struct Sword;
+
+#[test]
+fn on_related_succeeds_when_target_has_component() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let hand = app.world_mut().spawn(Hand).id();
+    app.world_mut().spawn((Sword, EquippedBy(hand)));
+}
+
+#[test]
+#[should_panic(expected = "but it was not found on target")]
+fn on_related_panics_when_target_lacks_component() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let foot = app.world_mut().spawn_empty().id();
+    app.world_mut().spawn((Sword, EquippedBy(foot)));
+}
+
+#[test]
+#[should_panic(expected = "has no integration::EquippedBy")]
+fn on_related_panics_without_relationship() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(Sword);
+}
+
+#[test]
+#[should_panic(expected = "does not exist")]
+fn on_related_panics_when_target_is_despawned() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let hand = app.world_mut().spawn(Hand).id();
+    app.world_mut().despawn(hand);
+    app.world_mut().spawn((Sword, EquippedBy(hand)));
+}
+
+#[test]
+fn validate_scene_root_reports_violations_in_subtree_only() {
+    let mut app = App::new();
+
+    let root = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, Velocity))
+        .id();
+    let child = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, ChildOf(root)))
+        .id();
+    let grandchild = app
+        .world_mut()
+        .spawn((SingleExpectation, ChildOf(child)))
+        .id();
+    app.world_mut().spawn((PhysicsBody,)); // Outside the scene
+
+    let violations = validate_scene_root(app.world(), root);
+
+    assert_eq!(violations.len(), 2);
+    assert!(violations
+        .iter()
+        .any(|violation| violation.entity == child && violation.missing.contains("Velocity")));
+    assert!(violations
+        .iter()
+        .any(|violation| violation.entity == grandchild && violation.missing.contains("Position")));
+}
+
+#[derive(Component, ExpectComponents)]
+#[require(Velocity)]
+#[expects(Velocity)]
+#[expect_include_required]
+struct Thruster;
+
+#[derive(Component, ExpectComponents)]
+#[require(Velocity)]
+#[expects(Velocity)]
+struct StrictThruster;
+
+#[test]
+fn include_required_treats_require_closure_as_satisfying() {
+    let mut app = App::new();
+
+    let entity = app.world_mut().spawn(Thruster).id();
+    app.world_mut().entity_mut(entity).remove::<Velocity>();
+
+    assert!(validate_scene_root(app.world(), entity).is_empty());
+}
+
+#[test]
+fn without_include_required_removed_required_component_is_missing() {
+    let mut app = App::new();
+
+    let entity = app.world_mut().spawn(StrictThruster).id();
+    app.world_mut().entity_mut(entity).remove::<Velocity>();
+
+    assert_eq!(validate_scene_root(app.world(), entity).len(), 1);
+}
+
+#[test]
+fn scene_world_audit_flags_violating_entity() {
+    let mut scene_world = World::new();
+    scene_world.spawn((PhysicsBody, Position, Velocity));
+    let broken = scene_world.spawn((PhysicsBody, Position)).id();
+
+    let violations = validate_scene_world(&scene_world);
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].entity, broken);
+    assert_eq!(violations[0].missing, std::any::type_name::<Velocity>());
+}
+
+#[test]
+fn validate_entity_returns_structured_violations() {
+    let mut world = World::new();
+    let valid = world.spawn((PhysicsBody, Position, Velocity)).id();
+    let broken = world.spawn((PhysicsBody, Position)).id();
+
+    assert_eq!(validate_entity(&world, valid), Ok(()));
+
+    let violations = validate_entity(&world, broken).unwrap_err();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].entity, broken);
+    assert_eq!(
+        violations[0].expecting,
+        std::any::type_name::<PhysicsBody>()
+    );
+    assert_eq!(violations[0].missing, std::any::type_name::<Velocity>());
+}
+
+#[test]
+fn validate_entity_in_uses_only_listed_registrations() {
+    let mut world = World::new();
+    let entity = world.spawn((PhysicsBody, SingleExpectation)).id();
+
+    let physics = ExpectRegistration::of::<PhysicsBody>();
+    let violations = validate_entity_in(&world, entity, &[&physics]);
+
+    assert_eq!(violations.len(), 2);
+    assert!(violations
+        .iter()
+        .all(|violation| violation.expecting == std::any::type_name::<PhysicsBody>()));
+    assert!(validate_entity_in(&world, entity, &[]).is_empty());
+}
+
+#[derive(Component)]
+struct NeverSubmitted;
+
+impl ExpectComponents for NeverSubmitted {
+    const EXPECTED: &'static [TypeId] = &[TypeId::of::<Position>()];
+    const EXPECTED_NAMES: &'static [&'static str] = &["Position"];
+}
+
+#[test]
+fn all_registrations_lists_expecting_types_with_their_expectations() {
+    let physics = all_registrations()
+        .find(|info| info.type_name == "integration::PhysicsBody")
+        .unwrap();
+    assert_eq!(
+        physics.expected,
+        ["integration::Position", "integration::Velocity"]
+    );
+
+    assert!(!all_registrations().any(|info| info.type_name == "integration::Position"));
+}
+
+#[test]
+fn is_registered_reports_collected_registrations() {
+    assert!(is_registered::<PhysicsBody>());
+    assert!(is_registered::<HandWritten>());
+    assert!(!is_registered::<NeverSubmitted>());
+}
+
+#[test]
+#[should_panic(expected = "NeverSubmitted expects Position but it was not found on entity")]
+fn manually_registered_types_are_validated() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.register::<NeverSubmitted>());
+
+    app.world_mut().spawn(NeverSubmitted);
+}
+
+#[test]
+fn manual_registration_of_collected_type_validates_once() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(ExpectedComponentsPlugin.register::<PhysicsBody>());
+
+    app.world_mut().spawn(PhysicsBody);
+
+    assert_eq!(
+        app.world().resource::<ValidationReport>().violations.len(),
+        1
+    );
+}
+
+#[test]
+fn manually_registered_types_are_validated_by_scheduled_pass() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(
+        ExpectedComponentsPlugin
+            .register::<NeverSubmitted>()
+            .validate_after_flush(Update),
+    );
+
+    app.world_mut().spawn(NeverSubmitted);
+    app.update();
+
+    let report = app.world().resource::<ValidationReport>();
+    assert!(report
+        .violations
+        .iter()
+        .any(|violation| violation.expecting == std::any::type_name::<NeverSubmitted>()));
+}
+
+#[test]
+fn registry_snapshot_is_stable_and_sorted() {
+    let snapshot = registry_snapshot();
+
+    assert_eq!(snapshot, registry_snapshot());
+    assert!(snapshot.contains(&format!(
+        "{}\n    expects {}\n    expects {}\n",
+        std::any::type_name::<PhysicsBody>(),
+        std::any::type_name::<Position>(),
+        std::any::type_name::<Velocity>()
+    )));
+
+    let type_lines: Vec<&str> = snapshot
+        .lines()
+        .filter(|line| !line.starts_with(' '))
+        .collect();
+    let mut sorted = type_lines.clone();
+    sorted.sort_unstable();
+    assert_eq!(type_lines, sorted);
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position, stability = "stable")]
+#[expects(Velocity, stability = "experimental")]
+#[expects(Static)]
+struct Replicated;
+
+#[test]
+fn stability_is_captured_in_registry() {
+    let expecting = std::any::type_name::<Replicated>();
+    let stability = |expected| expectation_stability(expecting, expected);
+
+    assert_eq!(
+        stability(std::any::type_name::<Position>()),
+        Some(Stability::Stable)
+    );
+    assert_eq!(
+        stability(std::any::type_name::<Velocity>()),
+        Some(Stability::Experimental)
+    );
+    assert_eq!(stability(std::any::type_name::<Static>()), None);
+    assert!(registry_snapshot().contains(&format!(
+        "expects {} (experimental)",
+        std::any::type_name::<Velocity>()
+    )));
+}
+
+#[derive(Component)]
+#[relationship(relationship_target = Connections)]
+struct ConnectedTo(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = ConnectedTo)]
+struct Connections(Vec<Entity>);
+
+#[derive(Component, ExpectComponents)]
+#[expect_incoming_relations(ConnectedTo, min = 2)]
+struct Hub;
+
+fn hub_with_connections(connections: usize) -> App {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let hub = app.world_mut().spawn(Hub).id();
+    for _ in 0..connections {
+        app.world_mut().spawn(ConnectedTo(hub));
+    }
+    app
+}
+
+#[test]
+fn incoming_relations_succeeds_at_minimum() {
+    hub_with_connections(2).update();
+}
+
+#[test]
+fn incoming_relations_succeeds_above_minimum() {
+    hub_with_connections(3).update();
+}
+
+#[test]
+#[should_panic(expected = "expects at least 2 incoming")]
+fn incoming_relations_panics_below_minimum() {
+    hub_with_connections(1).update();
+}
+
+#[test]
+fn incoming_relations_not_checked_at_insertion() {
+    // The hub spawns before anything links to it; only the frame pass judges it
+    let _app = hub_with_connections(0);
+}
+
+#[test]
+fn expectation_validator_lists_entities_broken_after_insertion() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.validate_after_flush(Update));
+
+    let broken = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, Velocity))
+        .id();
+    app.world_mut().spawn((PhysicsBody, Position, Velocity));
+    app.update();
+    app.world_mut().entity_mut(broken).remove::<Velocity>();
+
+    let violations = app
+        .world_mut()
+        .run_system_once(|validator: ExpectationValidator| {
+            validator.violations().collect::<Vec<_>>()
+        })
+        .unwrap();
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].entity, broken);
+    assert_eq!(violations[0].missing, std::any::type_name::<Velocity>());
+}
+
+#[test]
+fn validation_system_runs_once_without_plugin() {
+    let mut world = World::new();
+    let entity = world.spawn((PhysicsBody, Velocity)).id();
+    world.spawn((PhysicsBody, Position, Velocity));
+
+    let violations = world.run_system_once(validation_system).unwrap();
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].entity, entity);
+    assert!(violations[0].missing.ends_with("Position"));
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct VehicleExpectations;
+
+#[derive(Component, ExpectComponents)]
+#[expect_include(VehicleExpectations)]
+#[expects(Velocity)]
+struct Car;
+
+#[test]
+fn include_merges_expectations() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Car, Position, Velocity));
+}
+
+#[test]
+#[should_panic(expected = "Car expects integration::Position")]
+fn include_enforces_included_expectations() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Car, Velocity));
+}
+
+#[test]
+#[should_panic(expected = "Car expects integration::Velocity")]
+fn include_enforces_own_expectations() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Car, Position));
+}
+
+static CHANGED_ONLY_REPORTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_changed_only_report(message: &str) {
+    CHANGED_ONLY_REPORTS
+        .lock()
+        .unwrap()
+        .push(message.to_owned());
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct ChangeTracked(u32);
+
+#[test]
+fn changed_only_validates_each_change_once() {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Custom(record_changed_only_report));
+    app.add_plugins(
+        ExpectedComponentsPlugin
+            .validate_after_flush(Update)
+            .validate_changed_only(),
+    );
+
+    let entity = app.world_mut().spawn(ChangeTracked(0)).id();
+    app.update();
+    app.update();
+    app.update();
+    assert_eq!(CHANGED_ONLY_REPORTS.lock().unwrap().len(), 1);
+
+    app.world_mut().get_mut::<ChangeTracked>(entity).unwrap().0 += 1;
+    app.update();
+    app.update();
+    assert_eq!(CHANGED_ONLY_REPORTS.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn expect_current_validates_resource_entity() {
+    let mut world = World::new();
+    assert!(expect_current(&world).is_empty());
+
+    let valid = world.spawn((PhysicsBody, Position, Velocity)).id();
+    world.insert_resource(CurrentEntity(valid));
+    assert!(expect_current(&world).is_empty());
+
+    let invalid = world.spawn((PhysicsBody, Velocity)).id();
+    world.insert_resource(CurrentEntity(invalid));
+    let violations = expect_current(&world);
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].entity, invalid);
+}
+
+#[derive(Component, Default, PartialEq)]
+struct ServerConfig {
+    port: u16,
+}
+
+#[derive(Component, ExpectComponents)]
+#[expect_any(Position, Velocity)]
+struct EitherMotion;
+
+#[test]
+fn expect_any_succeeds_with_one_of_group() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((EitherMotion, Position));
+    app.world_mut().spawn((EitherMotion, Velocity));
+    app.world_mut().spawn((EitherMotion, Position, Velocity));
+}
+
+#[test]
+#[should_panic(
+    expected = "EitherMotion expects at least one of integration::Position, integration::Velocity on entity"
+)]
+fn expect_any_panics_with_none_of_group() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(EitherMotion);
+}
+
+#[derive(Component)]
+struct TeamRed;
+
+#[derive(Component)]
+struct TeamBlue;
+
+#[derive(Component)]
+struct TeamGreen;
+
+#[derive(Component, ExpectComponents)]
+#[expect_one_of(TeamRed, TeamBlue, TeamGreen)]
+struct TeamMember;
+
+#[test]
+fn expect_one_of_succeeds_with_exactly_one_of_group() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((TeamMember, TeamRed));
+    app.world_mut().spawn((TeamMember, TeamGreen));
+}
+
+#[test]
+fn expect_one_of_reports_how_many_were_found() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.with_failure_mode(FailureMode::Collect));
+
+    let none = app.world_mut().spawn(TeamMember).id();
+    let two = app.world_mut().spawn((TeamMember, TeamRed, TeamBlue)).id();
+
+    let messages: Vec<_> = app
+        .world()
+        .resource::<ValidationReport>()
+        .violations
+        .iter()
+        .map(|violation| violation.message.lines().next().unwrap().to_owned())
+        .collect();
+    let group = "integration::TeamRed, integration::TeamBlue, integration::TeamGreen";
+    assert_eq!(
+        messages,
+        [
+            format!("integration::TeamMember expects exactly one of {group} but found none on entity {none:?}"),
+            format!("integration::TeamMember expects exactly one of {group} but found 2 (integration::TeamRed, integration::TeamBlue) on entity {two:?}"),
+        ]
+    );
+}
+
+#[derive(Component, ExpectComponents)]
+#[forbids(Velocity)]
+struct Anchored;
+
+#[test]
+fn forbids_succeeds_without_forbidden_component() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Anchored, Position));
+}
+
+#[test]
+#[should_panic(expected = "Anchored forbids integration::Velocity but it was found on entity")]
+fn forbids_panics_when_forbidden_component_present() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Anchored, Velocity));
+}
+
+#[test]
+fn forbids_is_described_in_registry() {
+    assert!(registry_snapshot().contains(&format!(
+        "{}\n    forbids {}\n",
+        std::any::type_name::<Anchored>(),
+        std::any::type_name::<Velocity>()
+    )));
+}
+
+#[derive(Resource)]
+struct GridConfig;
+
+#[derive(Component, ExpectComponents)]
+#[expect_resource(GridConfig)]
+struct GridCell;
+
+#[test]
+fn expect_resource_succeeds_when_resource_present() {
+    let mut app = App::new();
+    app.insert_resource(GridConfig);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(GridCell);
+}
+
+#[test]
+#[should_panic(
+    expected = "GridCell expects resource integration::GridConfig but it is not present"
+)]
+fn expect_resource_panics_when_resource_missing() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(GridCell);
+}
+
+#[derive(Component)]
+struct Vitality(f32);
+
+#[derive(Component)]
+struct Nickname(String);
+
+fn is_named(name: &Nickname) -> bool {
+    !name.0.is_empty()
+}
+
+#[derive(Component, ExpectComponents)]
+#[expect_where(Vitality, |vitality: &Vitality| vitality.0 > 0.0)]
+#[expect_where(Nickname, is_named)]
+struct Hero;
+
+#[test]
+fn predicate_succeeds_when_value_passes() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut()
+        .spawn((Hero, Vitality(100.0), Nickname("hero".into())));
+}
+
+#[test]
+fn predicate_reports_value_that_fails() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.with_failure_mode(FailureMode::Collect));
+
+    let entity = app
+        .world_mut()
+        .spawn((Hero, Vitality(0.0), Nickname(String::new())))
+        .id();
+
+    let messages: Vec<String> = app
+        .world()
+        .resource::<ValidationReport>()
+        .violations
+        .iter()
+        .map(|violation| violation.message.lines().next().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            format!("integration::Hero expects integration::Vitality to satisfy its predicate but the value failed on entity {entity:?}"),
+            format!("integration::Hero expects integration::Nickname to satisfy its predicate but the value failed on entity {entity:?}"),
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "expects integration::Vitality but it was not found")]
+fn predicate_expects_presence() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Hero, Nickname("hero".into())));
+}
+
+#[test]
+fn predicate_revalidates_after_mutation_to_failing_value() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.with_failure_mode(FailureMode::Collect));
+
+    let entity = app
+        .world_mut()
+        .spawn((Hero, Vitality(100.0), Nickname("hero".into())))
+        .id();
+    app.update();
+    assert!(app
+        .world()
+        .resource::<ValidationReport>()
+        .violations
+        .is_empty());
+
+    app.world_mut().get_mut::<Vitality>(entity).unwrap().0 = 0.0;
+    app.update();
+
+    let messages: Vec<String> = app
+        .world()
+        .resource::<ValidationReport>()
+        .violations
+        .iter()
+        .map(|violation| violation.message.clone())
+        .collect();
+    assert_eq!(
+        messages,
+        [format!("integration::Hero expects integration::Vitality to satisfy its predicate, but it was changed to a failing value on entity {entity:?}")]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Vitality to satisfy its predicate but the value failed")]
+fn predicate_revalidates_mutation_in_changed_only_pass() {
+    let mut app = App::new();
+    app.add_plugins(
+        ExpectedComponentsPlugin
+            .validate_after_flush(Last)
+            .validate_changed_only(),
+    );
+
+    let entity = app
+        .world_mut()
+        .spawn((Hero, Vitality(100.0), Nickname("hero".into())))
+        .id();
+    app.update();
+
+    app.world_mut().get_mut::<Vitality>(entity).unwrap().0 = 0.0;
+    app.update();
+}
+
+#[derive(Component)]
+struct ChaseTarget;
+
+#[derive(Component)]
+struct PatrolRoute;
+
+#[derive(Component, ExpectComponents)]
+enum AiState {
+    Idle,
+    #[expects(ChaseTarget)]
+    Chasing,
+    #[expects(PatrolRoute)]
+    Patrolling {
+        _waypoint: usize,
+    },
+}
+
+#[test]
+fn variant_expectations_apply_only_to_their_variant() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(AiState::Idle);
+    app.world_mut().spawn((AiState::Chasing, ChaseTarget));
+    app.world_mut()
+        .spawn((AiState::Patrolling { _waypoint: 0 }, PatrolRoute));
+}
+
+#[test]
+fn variant_expectation_reports_missing_component() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.with_failure_mode(FailureMode::Collect));
+
+    let entity = app.world_mut().spawn(AiState::Chasing).id();
+
+    let violations = &app.world().resource::<ValidationReport>().violations;
+    assert_eq!(violations.len(), 1);
+    assert_eq!(
+        violations[0].message.lines().next().unwrap(),
+        format!("integration::AiState::Chasing expects integration::ChaseTarget but it was not found on entity {entity:?}")
+    );
+}
+
+#[derive(Component, ExpectComponents)]
+#[expect_non_default(ServerConfig)]
+struct Server;
+
+#[test]
+fn non_default_succeeds_when_configured() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Server, ServerConfig { port: 8080 }));
+}
+
+#[test]
+#[should_panic(expected = "to differ from its default value")]
+fn non_default_panics_when_left_at_default() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Server, ServerConfig::default()));
+}
+
+#[test]
+#[should_panic(expected = "ServerConfig but it was not found")]
+fn non_default_expects_presence() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(Server);
+}
+
+#[test]
+#[should_panic(expected = "ServerConfig to differ from its default value, but it was changed back")]
+fn non_default_revalidates_after_mutation_to_default() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app
+        .world_mut()
+        .spawn((Server, ServerConfig { port: 8080 }))
+        .id();
+    app.update();
+
+    *app.world_mut().get_mut::<ServerConfig>(entity).unwrap() = ServerConfig::default();
+    app.update();
+}
+
+#[test]
+#[should_panic(expected = "ServerConfig to differ from its default value on entity")]
+fn non_default_revalidates_mutation_in_changed_only_pass() {
+    let mut app = App::new();
+    app.add_plugins(
+        ExpectedComponentsPlugin
+            .validate_after_flush(Last)
+            .validate_changed_only(),
+    );
+
+    let entity = app
+        .world_mut()
+        .spawn((Server, ServerConfig { port: 8080 }))
+        .id();
+    app.update();
+
+    *app.world_mut().get_mut::<ServerConfig>(entity).unwrap() = ServerConfig::default();
+    app.update();
+}
+
+#[test]
+fn non_default_allows_mutation_to_other_value() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app
+        .world_mut()
+        .spawn((Server, ServerConfig { port: 8080 }))
+        .id();
+    app.update();
+
+    app.world_mut()
+        .get_mut::<ServerConfig>(entity)
+        .unwrap()
+        .port = 9090;
+    app.update();
+}
+
+static GATED_REPORTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_gated_report(message: &str) {
+    GATED_REPORTS.lock().unwrap().push(message.to_owned());
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct Gated;
+
+#[test]
+fn validation_gate_skips_rejected_entities() {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Custom(record_gated_report));
+    app.insert_resource(ValidationGate::new(|_, entity| entity.index_u32() % 2 == 1));
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entities: Vec<Entity> = (0..6).map(|_| app.world_mut().spawn(Gated).id()).collect();
+
+    let reports = GATED_REPORTS.lock().unwrap();
+    let odd: Vec<&Entity> = entities
+        .iter()
+        .filter(|entity| entity.index_u32() % 2 == 1)
+        .collect();
+    assert_eq!(reports.len(), odd.len());
+    for entity in odd {
+        assert!(reports.iter().any(|report| report
+            .lines()
+            .next()
+            .unwrap()
+            .ends_with(&format!("{entity:?}"))));
+    }
+}
+
+#[derive(Component, ExpectComponents)]
+#[expect_all_children(Position)]
+struct PhysicsWorld;
+
+fn physics_world_with_children(children_with_position: usize, children_without: usize) -> App {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let parent = app.world_mut().spawn(PhysicsWorld).id();
+    for _ in 0..children_with_position {
+        app.world_mut().spawn((Position, ChildOf(parent)));
+    }
+    for _ in 0..children_without {
+        app.world_mut().spawn(ChildOf(parent));
+    }
+    app
+}
+
+#[test]
+fn all_children_succeeds_when_every_child_matches() {
+    physics_world_with_children(3, 0).update();
+}
+
+#[test]
+#[should_panic(
+    expected = "expects integration::Position on all children, but it was not found on child"
+)]
+fn all_children_panics_naming_the_invalid_child() {
+    physics_world_with_children(2, 1).update();
+}
+
+#[derive(Component)]
+struct Slot;
+
+#[derive(Component, ExpectComponents)]
+#[expect_children(Slot, min = 9, max = 10)]
+struct Hotbar;
+
+fn hotbar_with_slots(slots: usize) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.with_failure_mode(FailureMode::Collect));
+
+    let hotbar = app.world_mut().spawn(Hotbar).id();
+    for _ in 0..slots {
+        app.world_mut().spawn((Slot, ChildOf(hotbar)));
+    }
+    app.world_mut().spawn(ChildOf(hotbar));
+    app.update();
+    (app, hotbar)
+}
+
+fn collected_messages(app: &App) -> Vec<String> {
+    app.world()
+        .resource::<ValidationReport>()
+        .violations
+        .iter()
+        .map(|violation| violation.message.lines().next().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn child_count_succeeds_within_bounds() {
+    let (app, _) = hotbar_with_slots(9);
+    assert!(app.world().resource::<ValidationReport>().is_empty());
+}
+
+#[test]
+fn child_count_reports_too_few_matching_children() {
+    let (app, hotbar) = hotbar_with_slots(4);
+    assert_eq!(
+        collected_messages(&app),
+        vec![format!(
+            "integration::Hotbar expects at least 9 children with integration::Slot but found 4 on entity {hotbar:?}"
+        )]
+    );
+}
+
+#[test]
+fn child_count_reports_too_many_matching_children() {
+    let (app, hotbar) = hotbar_with_slots(11);
+    assert_eq!(
+        collected_messages(&app),
+        vec![format!(
+            "integration::Hotbar expects at most 10 children with integration::Slot but found 11 on entity {hotbar:?}"
+        )]
+    );
+}
+
+#[test]
+fn child_count_reports_each_failure_once_until_fixed() {
+    let (mut app, hotbar) = hotbar_with_slots(4);
+    app.update();
+    app.update();
+    assert_eq!(collected_messages(&app).len(), 1);
+
+    // Fixed and broken again, so reported again
+    let slots: Vec<Entity> = (0..5)
+        .map(|_| app.world_mut().spawn((Slot, ChildOf(hotbar))).id())
+        .collect();
+    app.update();
+    for slot in slots {
+        app.world_mut().despawn(slot);
+    }
+    app.update();
+    app.update();
+    assert_eq!(
+        collected_messages(&app),
+        vec![
+            format!("integration::Hotbar expects at least 9 children with integration::Slot but found 4 on entity {hotbar:?}"),
+            format!("integration::Hotbar expects at least 9 children with integration::Slot but found 4 on entity {hotbar:?}"),
+        ]
+    );
+}
+
+#[derive(Component)]
+struct ListContainer;
+
+#[derive(Component, ExpectComponents)]
+#[expect_on_parent(ListContainer)]
+struct ListItem;
+
+#[test]
+fn on_parent_succeeds_when_parent_matches() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let list = app.world_mut().spawn(ListContainer).id();
+    app.world_mut().spawn((ListItem, ChildOf(list)));
+}
+
+#[test]
+#[should_panic(
+    expected = "ListItem expects integration::ListContainer on its parent, but it was not found on parent"
+)]
+fn on_parent_panics_when_parent_lacks_component() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let list = app.world_mut().spawn_empty().id();
+    app.world_mut().spawn((ListItem, ChildOf(list)));
+}
+
+#[test]
+#[should_panic(expected = "ListItem expects a parent with integration::ListContainer, but entity")]
+fn on_parent_panics_without_parent() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(ListItem);
+}
+
+static BUDGETED_REPORTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_budgeted_report(message: &str) {
+    BUDGETED_REPORTS.lock().unwrap().push(message.to_owned());
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct Budgeted;
+
+#[test]
+fn time_budget_spreads_validation_across_frames() {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Custom(record_budgeted_report));
+    app.insert_resource(ValidationTimeBudget(Duration::ZERO));
+    app.add_plugins(ExpectedComponentsPlugin.validate_after_flush(Update));
+
+    let entities: Vec<Entity> = (0..4)
+        .map(|_| app.world_mut().spawn(Budgeted).id())
+        .collect();
+
+    // A zero budget still makes progress, one entity per frame
+    app.update();
+    assert_eq!(BUDGETED_REPORTS.lock().unwrap().len(), 1);
+
+    for _ in 1..entities.len() {
+        app.update();
+    }
+
+    let reports = BUDGETED_REPORTS.lock().unwrap();
+    assert_eq!(reports.len(), entities.len());
+    for entity in &entities {
+        assert!(reports.iter().any(|report| report
+            .lines()
+            .next()
+            .unwrap()
+            .ends_with(&format!("{entity:?}"))));
+    }
+}
+
+#[test]
+fn spawn_checked_spawns_valid_bundle() {
+    let mut world = World::new();
+
+    let entity = world
+        .spawn_checked((PhysicsBody, Position, Velocity))
+        .unwrap()
+        .id();
+
+    assert!(world.entity(entity).contains::<PhysicsBody>());
+}
+
+#[test]
+fn spawn_checked_rejects_invalid_bundle_without_spawning() {
+    let mut world = World::new();
+
+    let Err(report) = world.spawn_checked((PhysicsBody, Velocity)) else {
+        panic!("expected the bundle to be rejected");
+    };
+
+    assert_eq!(report.violations.len(), 1);
+    assert!(report.violations[0].missing.ends_with("Position"));
+    assert!(report.to_string().contains("not found in the bundle"));
+    assert_eq!(world.query::<&PhysicsBody>().iter(&world).count(), 0);
+}
+
+#[test]
+fn spawn_checked_counts_required_components() {
+    let mut world = World::new();
+
+    assert!(world.spawn_checked(Thruster).is_ok());
+}
+
+#[derive(Bundle)]
+struct IncompletePhysicsBundle {
+    body: PhysicsBody,
+    position: Position,
+}
+
+#[test]
+fn validate_bundle_accepts_complete_bundle() {
+    assert!(validate_bundle::<(PhysicsBody, Position, Velocity)>().is_ok());
+}
+
+#[test]
+fn validate_bundle_reports_components_the_bundle_never_includes() {
+    let report = validate_bundle::<IncompletePhysicsBundle>().unwrap_err();
+
+    assert_eq!(report.violations.len(), 1);
+    assert_eq!(report.violations[0].expecting, "integration::PhysicsBody");
+    assert_eq!(report.violations[0].missing, "integration::Velocity");
+}
+
+#[test]
+fn spawn_checked_works_through_commands() {
+    let mut world = World::new();
+
+    let mut commands = world.commands();
+    assert!(commands.spawn_checked((PhysicsBody, Position)).is_err());
+    assert!(commands
+        .spawn_checked((PhysicsBody, Position, Velocity))
+        .is_ok());
+    world.flush();
+
+    assert_eq!(world.query::<&PhysicsBody>().iter(&world).count(), 1);
+}
+
+#[test]
+fn spawn_validated_returns_violations_instead_of_panicking() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    let world = app.world_mut();
+
+    let Err(violations) = world.spawn_validated((PhysicsBody, Velocity)) else {
+        panic!("expected the bundle to be rejected");
+    };
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].missing, std::any::type_name::<Position>());
+    assert_eq!(world.query::<&PhysicsBody>().iter(world).count(), 0);
+
+    let entity = world
+        .spawn_validated((PhysicsBody, Position, Velocity))
+        .unwrap();
+    assert!(world.entity(entity).contains::<PhysicsBody>());
+    assert!(validation_enabled(world));
+}
+
+#[test]
+fn spawn_expecting_panics_inline_regardless_of_failure_mode() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.with_failure_mode(FailureMode::Collect));
+
+    let entity = app
+        .world_mut()
+        .spawn_expecting((PhysicsBody, Position, Velocity))
+        .id();
+    assert!(app.world().entity(entity).contains::<Velocity>());
+
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        app.world_mut().spawn_expecting((PhysicsBody, Velocity));
+    }))
+    .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(message.contains("expects integration::Position but it was not found"));
+    // Validated inline only, so the hooks collected nothing
+    assert!(app
+        .world()
+        .resource::<ValidationReport>()
+        .violations
+        .is_empty());
+}
+
+#[test]
+#[should_panic(expected = "expects integration::Position")]
+fn spawn_expecting_works_through_commands() {
+    let mut world = World::new();
+
+    world.commands().spawn_expecting((PhysicsBody, Velocity));
+}
+
+#[test]
+fn spawn_validated_checks_more_than_presence_on_world() {
+    let mut world = World::new();
+
+    assert!(world.spawn_validated(GridCell).is_err());
+    world.insert_resource(GridConfig);
+    assert!(world.spawn_validated(GridCell).is_ok());
+}
+
+#[test]
+fn spawn_validated_works_through_commands() {
+    let mut world = World::new();
+
+    let mut commands = world.commands();
+    assert!(commands.spawn_validated((PhysicsBody, Position)).is_err());
+    let entity = commands
+        .spawn_validated((PhysicsBody, Position, Velocity))
+        .unwrap();
+    world.flush();
+
+    assert!(world.entity(entity).contains::<PhysicsBody>());
+}
+
+#[derive(Component, Default)]
+#[component(storage = "SparseSet")]
+struct SparseMarker;
+
+#[derive(Component, ExpectComponents)]
+#[expects(SparseMarker)]
+struct ExpectsSparse;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position, SparseMarker)]
+struct ExpectsMixed;
+
+#[derive(Component, ExpectComponents)]
+#[component(storage = "SparseSet")]
+#[expects(Position, SparseMarker)]
+struct SparseExpectsMixed;
+
+#[test]
+fn storage_combinations_succeed_when_present() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((ExpectsSparse, SparseMarker));
+    app.world_mut()
+        .spawn((ExpectsMixed, Position, SparseMarker));
+    app.world_mut()
+        .spawn((SparseExpectsMixed, Position, SparseMarker));
+}
+
+#[test]
+#[should_panic(expected = "ExpectsSparse expects integration::SparseMarker")]
+fn table_expecting_sparse_panics_when_missing() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    // Another entity owning the sparse set must not satisfy this one
+    app.world_mut().spawn(SparseMarker);
+    app.world_mut().spawn(ExpectsSparse);
+}
+
+#[test]
+#[should_panic(expected = "ExpectsMixed expects integration::Position")]
+fn mixed_storage_panics_when_table_component_missing() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((ExpectsMixed, SparseMarker));
+}
+
+#[test]
+#[should_panic(expected = "SparseExpectsMixed expects integration::SparseMarker")]
+fn sparse_expecting_panics_when_sparse_component_missing() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((SparseExpectsMixed, Position));
+}
+
+#[test]
+fn expectation_lookup_finds_expectations_by_type_id_and_name() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    let lookup = app.world().resource::<ExpectationLookup>();
+
+    let expected = [
+        std::any::type_name::<Position>(),
+        std::any::type_name::<Velocity>(),
+    ];
+    assert_eq!(
+        lookup.expected_components_for(TypeId::of::<PhysicsBody>()),
+        Some(&expected[..])
+    );
+    assert_eq!(
+        lookup.expected_components_for_name("integration::PhysicsBody"),
+        Some(&expected[..])
+    );
+    assert_eq!(
+        lookup.expected_components_for(TypeId::of::<Position>()),
+        None
+    );
+    assert_eq!(
+        lookup.expected_components_for_name("integration::Missing"),
+        None
+    );
+}
+
+#[test]
+fn expectation_graph_lists_expected_components_by_type() {
+    let graph = expectation_graph();
+
+    let physics = graph
+        .iter()
+        .find(|(expecting, _)| *expecting == std::any::type_name::<PhysicsBody>())
+        .unwrap();
+    assert_eq!(
+        physics.1,
+        [
+            std::any::type_name::<Position>(),
+            std::any::type_name::<Velocity>()
+        ]
+    );
+
+    let names: Vec<&str> = graph.iter().map(|(expecting, _)| *expecting).collect();
+    let mut sorted = names.clone();
+    sorted.sort_unstable();
+    assert_eq!(names, sorted);
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(CycleEnd)]
+struct CycleStart;
+
+#[derive(Component, ExpectComponents)]
+#[expects(CycleStart)]
+struct CycleEnd;
+
+#[test]
+#[should_panic(
+    expected = "expectations form a cycle: integration::CycleEnd -> integration::CycleStart -> integration::CycleEnd"
+)]
+fn deny_expectation_cycles_panics_naming_the_cycle() {
+    let registry = ExpectationRegistry::new()
+        .with::<CycleStart>()
+        .with::<CycleEnd>()
+        .with::<PhysicsBody>();
+
+    App::new().add_plugins(
+        ExpectedComponentsPlugin
+            .with_registry(registry)
+            .deny_expectation_cycles(),
+    );
+}
+
+#[test]
+fn deny_expectation_cycles_accepts_acyclic_expectations() {
+    let registry = ExpectationRegistry::new()
+        .with::<PhysicsBody>()
+        .with::<VehicleExpectations>();
+
+    App::new().add_plugins(
+        ExpectedComponentsPlugin
+            .with_registry(registry)
+            .deny_expectation_cycles(),
+    );
+}
+
+#[test]
+fn components_in_a_cycle_are_valid_when_inserted_together() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((CycleStart, CycleEnd));
+}
+
+#[test]
+fn registry_to_dot_declares_nodes_and_edges() {
+    let dot = registry_to_dot();
+
+    assert!(dot.starts_with("digraph expectations {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains("    \"integration::PhysicsBody\";\n"));
+    assert!(dot.contains("    \"integration::Position\";\n"));
+    assert!(dot.contains("    \"integration::PhysicsBody\" -> \"integration::Position\";\n"));
+    assert!(dot.contains("    \"integration::PhysicsBody\" -> \"integration::Velocity\";\n"));
+}
+
+#[test]
+#[should_panic(expected = "PhysicsBody expects")]
+fn skip_bevy_internal_still_validates_user_types() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.skip_bevy_internal());
+
+    app.world_mut().spawn((PhysicsBody, Velocity));
+}
+
+#[test]
+fn development_preset_panics_with_entity_dump_and_backtrace() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin::development());
+
+    assert_eq!(current_failure_mode(app.world()), FailureMode::Panic);
+    assert!(app.world().contains_resource::<VerboseFailures>());
+    assert!(app.world().contains_resource::<FailureBacktraces>());
+    assert!(!app.world().contains_resource::<ViolationRateLimit>());
+}
+
+#[test]
+#[should_panic(expected = "PhysicsBody expects")]
+fn development_preset_panics_at_insert() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin::development());
+
+    app.world_mut().spawn((PhysicsBody, Velocity));
+}
+
+#[test]
+fn ci_preset_collects_into_json_report_with_error_budget() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin::ci());
+
+    assert_eq!(current_failure_mode(app.world()), FailureMode::Collect);
+    assert_eq!(
+        *app.world().resource::<JsonReport>(),
+        JsonReport("expectation_report.json".into())
+    );
+    assert_eq!(*app.world().resource::<ErrorBudget>(), ErrorBudget(0));
+}
+
+#[test]
+fn ci_preset_keeps_resources_inserted_before_it() {
+    let path = std::env::temp_dir().join("bevy_expected_components_ci_preset.json");
+    let mut app = App::new();
+    app.insert_resource(JsonReport(path.clone()));
+    app.insert_resource(ErrorBudget(3));
+    app.add_plugins(ExpectedComponentsPlugin::ci());
+
+    assert_eq!(*app.world().resource::<JsonReport>(), JsonReport(path));
+    assert_eq!(*app.world().resource::<ErrorBudget>(), ErrorBudget(3));
+}
+
+#[test]
+fn json_report_lists_collected_failures() {
+    let path = std::env::temp_dir().join("bevy_expected_components_json_report.json");
+    let mut app = App::new();
+    app.insert_resource(JsonReport(path.clone()));
+    app.insert_resource(ErrorBudget(1));
+    app.add_plugins(ExpectedComponentsPlugin::ci());
+
+    let entity = app.world_mut().spawn(PhysicsBody).id();
+    app.update();
+
+    let caller = app.world().resource::<ValidationReport>().violations[0]
+        .caller
+        .map_or_else(|| "null".to_owned(), |caller| format!("\"{caller}\""));
+    let json = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        json,
+        format!(
+            "{{\"violations\":[{{\"entity\":\"{entity}\",\"expecting\":\"integration::PhysicsBody\",\"missing\":\"integration::Position\",\"message\":\"integration::PhysicsBody expects integration::Position, integration::Velocity but they were not found on entity {entity}\\nentity {entity} currently has: integration::PhysicsBody\",\"caller\":{caller}}}]}}"
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "2 expectation failures exceed the error budget of 1")]
+fn error_budget_fails_the_frame_that_exceeds_it() {
+    let mut app = App::new();
+    app.insert_resource(ErrorBudget(1));
+    app.add_plugins(ExpectedComponentsPlugin.with_failure_mode(FailureMode::Collect));
+
+    app.world_mut().spawn((PhysicsBody, Velocity));
+    app.update();
+    app.world_mut().spawn((PhysicsBody, Velocity));
+    app.update();
+}
+
+#[test]
+fn production_preset_warns_once_with_rate_limit_and_stats() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin::production());
+
+    assert_eq!(current_failure_mode(app.world()), FailureMode::WarnOnce);
+    assert_eq!(
+        *app.world().resource::<ViolationRateLimit>(),
+        ViolationRateLimit(10)
+    );
+    assert!(app.world().contains_resource::<ValidationStats>());
+
+    app.world_mut().spawn((PhysicsBody, Position, Velocity));
+    app.update();
+    assert_eq!(
+        validation_stats_by_type(app.world())["integration::PhysicsBody"].validations,
+        1
+    );
+}
+
+#[test]
+fn validate_entity_accepts_complete_entity_before_save() {
+    let mut world = World::new();
+    let entity = world.spawn((PhysicsBody, Position, Velocity)).id();
+
+    assert!(validate_entity(&world, entity).is_ok());
+}
+
+#[test]
+fn validate_entity_flags_incomplete_entity_after_load() {
+    let mut world = World::new();
+
+    // Simulates a save that lost Velocity: components are restored one at a time
+    let entity = world.spawn_empty().id();
+    world.entity_mut(entity).insert(Position);
+    world.entity_mut(entity).insert(PhysicsBody);
+
+    let violations = validate_entity(&world, entity).unwrap_err();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].entity, entity);
+    assert!(violations[0].missing.ends_with("Velocity"));
+}
+
+#[test]
+fn expectation_status_reports_satisfied_and_missing() {
+    let mut world = World::new();
+    let valid = world.spawn((PhysicsBody, Position, Velocity)).id();
+    let invalid = world.spawn((PhysicsBody, Velocity)).id();
+    world.spawn(Position);
+
+    let mut query = world.query::<(Entity, ExpectationStatus<PhysicsBody>)>();
+    let statuses: HashMap<Entity, (bool, Vec<&str>)> = query
+        .iter(&world)
+        .map(|(entity, status)| (entity, (status.is_satisfied(), status.missing())))
+        .collect();
+
+    assert_eq!(statuses.len(), 2);
+    assert_eq!(statuses[&valid], (true, vec![]));
+    assert!(!statuses[&invalid].0);
+    assert_eq!(statuses[&invalid].1.len(), 1);
+    assert!(statuses[&invalid].1[0].ends_with("Position"));
+}
+
+static DEADLINE_REPORTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_deadline_report(message: &str) {
+    DEADLINE_REPORTS.lock().unwrap().push(message.to_owned());
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct HalfBuilt;
+
+#[test]
+fn completion_deadline_reports_only_after_deadline() {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Custom(record_deadline_report));
+    app.insert_resource(CompletionDeadline(100));
+    app.add_plugins(ExpectedComponentsPlugin.validate_after_flush(Update));
+
+    app.world_mut().spawn(HalfBuilt);
+    app.update();
+    app.update();
+    assert!(DEADLINE_REPORTS.lock().unwrap().is_empty());
+
+    for _ in 0..100 {
+        app.world_mut().increment_change_tick();
+    }
+    app.update();
+    assert_eq!(DEADLINE_REPORTS.lock().unwrap().len(), 1);
+}
+
+#[derive(Resource)]
+struct DefaultSpeed(f32);
+
+#[derive(Component, Debug, PartialEq)]
+struct Speed(f32);
+
+#[derive(Component, ExpectComponents)]
+#[expects(Speed)]
+struct Racer;
+
+#[test]
+fn recovery_inserts_missing_component_using_world() {
+    let mut app = App::new();
+    app.insert_resource(DefaultSpeed(3.0));
+    app.add_plugins(ExpectedComponentsPlugin);
+    register_recovery::<Speed>(app.world_mut(), |world, _| {
+        Speed(world.resource::<DefaultSpeed>().0)
+    });
+
+    let entity = app.world_mut().spawn(Racer).id();
+    app.world_mut().flush();
+
+    assert_eq!(app.world().get::<Speed>(entity), Some(&Speed(3.0)));
+}
+
+#[test]
+fn recovery_leaves_present_component_alone() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    register_recovery::<Speed>(app.world_mut(), |_, _| Speed(0.0));
+
+    let entity = app.world_mut().spawn((Racer, Speed(7.0))).id();
+    app.world_mut().flush();
+
+    assert_eq!(app.world().get::<Speed>(entity), Some(&Speed(7.0)));
+}
+
+#[test]
+fn validation_stats_count_each_type_independently() {
+    let mut app = App::new();
+    app.init_resource::<ValidationStats>();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    for _ in 0..3 {
+        app.world_mut().spawn((PhysicsBody, Position, Velocity));
+    }
+    app.world_mut().spawn((SingleExpectation, Position));
+
+    let stats = validation_stats_by_type(app.world());
+    assert_eq!(stats["integration::PhysicsBody"].validations, 3);
+    assert_eq!(stats["integration::SingleExpectation"].validations, 1);
+}
+
+#[test]
+fn validation_stats_empty_without_resource() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((PhysicsBody, Position, Velocity));
+
+    assert!(validation_stats_by_type(app.world()).is_empty());
+}
+
+fn procedural_expected() -> &'static [TypeId] {
+    static IDS: [TypeId; 2] = [TypeId::of::<Position>(), TypeId::of::<Velocity>()];
+    &IDS
+}
+
+#[derive(Component, ExpectComponents)]
+#[expect_fn(procedural_expected)]
+struct Procedural;
+
+#[test]
+fn expect_fn_supplies_expected_components() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Procedural, Position, Velocity));
+
+    assert_eq!(Procedural::expected_components(), procedural_expected());
+    assert_eq!(Procedural::expected_component_names().len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Procedural expects integration::Velocity but it was not found")]
+fn expect_fn_enforces_function_supplied_components() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    app.world_mut().register_component::<Velocity>();
+
+    app.world_mut().spawn((Procedural, Position));
+}
+
+#[test]
+fn expect_fn_components_are_named_once_registered() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    app.world_mut().spawn((Procedural, Position, Velocity));
+
+    assert_eq!(
+        Procedural::expected_component_names(),
+        ["integration::Position", "integration::Velocity"]
+    );
+}
+
+#[derive(Component)]
+struct ScriptedUnit;
+
+fn app_with_named_expectations() -> App {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.with_failure_mode(FailureMode::Collect));
+    app.world_mut().register_component::<ScriptedUnit>();
+    register_named_expectation(
+        app.world_mut(),
+        TypeId::of::<ScriptedUnit>(),
+        "integration::Velocity",
+    );
+    register_named_expectation(
+        app.world_mut(),
+        TypeId::of::<ScriptedUnit>(),
+        "mods::Faction",
+    );
+    app
+}
+
+#[test]
+fn named_expectation_resolves_registered_component_by_name() {
+    let mut app = app_with_named_expectations();
+
+    let entity = app.world_mut().spawn((ScriptedUnit, Velocity)).id();
+
+    let violations = &app.world().resource::<ValidationReport>().violations;
+    assert_eq!(violations.len(), 1);
+    assert_eq!(
+        violations[0].message.lines().next().unwrap(),
+        format!("integration::ScriptedUnit expects mods::Faction but it was not found on entity {entity:?}")
+    );
+}
+
+#[test]
+fn named_expectations_report_every_missing_name() {
+    let mut app = app_with_named_expectations();
+
+    let entity = app.world_mut().spawn(ScriptedUnit).id();
+
+    let violations = &app.world().resource::<ValidationReport>().violations;
+    assert_eq!(violations.len(), 1);
+    assert_eq!(
+        violations[0].message.lines().next().unwrap(),
+        format!("integration::ScriptedUnit expects integration::Velocity, mods::Faction but they were not found on entity {entity:?}")
+    );
+}
+
+#[test]
+fn named_expectation_registered_twice_is_checked_once() {
+    let mut app = app_with_named_expectations();
+    register_named_expectation(
+        app.world_mut(),
+        TypeId::of::<ScriptedUnit>(),
+        "integration::Velocity",
+    );
+
+    let entity = app.world_mut().spawn(ScriptedUnit).id();
+
+    assert_eq!(
+        collected_messages(&app),
+        [format!("integration::ScriptedUnit expects integration::Velocity, mods::Faction but they were not found on entity {entity:?}")]
+    );
+}
+
+#[derive(Component)]
+struct Allegiance;
+
+#[test]
+fn named_expectation_resolves_component_registered_after_first_check() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.with_failure_mode(FailureMode::Collect));
+    app.world_mut().register_component::<ScriptedUnit>();
+    register_named_expectation(
+        app.world_mut(),
+        TypeId::of::<ScriptedUnit>(),
+        "integration::Allegiance",
+    );
+
+    app.world_mut().spawn(ScriptedUnit);
+    app.world_mut().spawn((ScriptedUnit, Allegiance));
+
+    assert_eq!(collected_messages(&app).len(), 1);
+}
+
+#[derive(Component)]
+struct PlayerMarker;
+
+#[derive(Component)]
+struct Health;
+
+fn app_with_players(with_health: usize, without_health: usize) -> App {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    register_single_expectation::<PlayerMarker, Health>(app.world_mut());
+
+    for _ in 0..with_health {
+        app.world_mut().spawn((PlayerMarker, Health));
+    }
+    for _ in 0..without_health {
+        app.world_mut().spawn(PlayerMarker);
+    }
+    app
+}
+
+#[test]
+fn single_expectation_succeeds_with_one_valid_singleton() {
+    app_with_players(1, 0).update();
+}
+
+#[test]
+#[should_panic(expected = "PlayerMarker expects its singleton entity to have integration::Health")]
+fn single_expectation_panics_when_singleton_incomplete() {
+    app_with_players(0, 1).update();
+}
+
+#[test]
+#[should_panic(expected = "PlayerMarker expects exactly one entity with it, but found none")]
+fn single_expectation_panics_with_no_singleton() {
+    app_with_players(0, 0).update();
+}
+
+#[test]
+#[should_panic(expected = "PlayerMarker expects exactly one entity with it, but found 2")]
+fn single_expectation_panics_with_multiple_matches() {
+    app_with_players(2, 0).update();
+}
+
+#[test]
+fn single_expectation_reports_each_failure_once() {
+    let mut app = app_with_players(0, 1);
+    app.insert_resource(FailureMode::Collect);
+    app.update();
+    app.update();
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<ValidationReport>().violations.len(),
+        1
+    );
+}
+
+#[test]
+fn single_expectation_waits_out_grace_period_for_missing_singleton() {
+    let mut app = app_with_players(0, 0);
+    app.insert_resource(FailureMode::Collect);
+    app.insert_resource(SingletonGracePeriod(2));
+    app.update();
+    app.update();
+    assert!(app.world().resource::<ValidationReport>().is_empty());
+
+    app.update();
+    assert_eq!(
+        collected_messages(&app),
+        ["integration::PlayerMarker expects exactly one entity with it, but found none"]
+    );
+}
+
+#[test]
+fn single_expectation_accepts_singleton_spawned_within_grace_period() {
+    let mut app = app_with_players(0, 0);
+    app.insert_resource(SingletonGracePeriod(2));
+    app.update();
+
+    app.world_mut().spawn((PlayerMarker, Health));
+    app.update();
+    app.update();
+}
+
+mod collision {
+    use bevy_ecs::component::Component;
+
+    #[derive(Component)]
+    pub struct Body;
+}
+
+mod render {
+    use bevy_ecs::component::Component;
+    use bevy_expected_components::ExpectComponents;
+
+    #[derive(Component, ExpectComponents)]
+    #[expects(super::collision::Body)]
+    pub struct Body;
+}
+
+#[test]
+#[should_panic(expected = "render::Body expects integration::collision::Body")]
+fn type_can_expect_a_different_type_with_the_same_name() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((render::Body, collision::Body));
+    app.world_mut().spawn(render::Body);
+}
+
+mod vehicles {
+    use bevy_ecs::component::Component;
+    use bevy_expected_components::ExpectComponents;
+
+    use super::{Position, Velocity};
+
+    #[derive(Component, ExpectComponents)]
+    #[expects(Position)]
+    pub struct Car;
+
+    #[derive(Component, ExpectComponents)]
+    #[expects(Velocity)]
+    pub struct Boat;
+
+    #[derive(Component, ExpectComponents)]
+    #[expects(Position, Velocity)]
+    pub struct Plane;
+}
+
+fn vehicle_world() -> World {
+    let mut world = World::new();
+    register_module_expectations!(&mut world, vehicles::Car, vehicles::Boat, vehicles::Plane);
+    world
+}
+
+#[test]
+fn module_registration_succeeds_with_expected_components() {
+    let mut world = vehicle_world();
+
+    world.spawn((vehicles::Car, Position));
+    world.spawn((vehicles::Boat, Velocity));
+    world.spawn((vehicles::Plane, Position, Velocity));
+}
+
+#[test]
+#[should_panic(expected = "Car expects integration::Position")]
+fn module_registration_validates_first_type() {
+    vehicle_world().spawn(vehicles::Car);
+}
+
+#[test]
+#[should_panic(expected = "Boat expects integration::Velocity")]
+fn module_registration_validates_second_type() {
+    vehicle_world().spawn(vehicles::Boat);
+}
+
+#[test]
+#[should_panic(expected = "Plane expects integration::Velocity")]
+fn module_registration_validates_third_type() {
+    vehicle_world().spawn((vehicles::Plane, Position));
+}
+
+#[test]
+fn module_registration_combines_with_plugin() {
+    let mut app = App::new();
+    register_module_expectations!(app.world_mut(), vehicles::Car);
+    app.add_plugins(ExpectedComponentsPlugin);
+    register_module_expectations!(app.world_mut(), vehicles::Car, vehicles::Boat);
+
+    app.world_mut().spawn((vehicles::Car, Position));
+}
+
+static CONCURRENT_MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_concurrent(message: &str) {
+    CONCURRENT_MESSAGES.lock().unwrap().push(message.to_owned());
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct ConcurrentProbe;
+
+#[test]
+fn worlds_on_separate_threads_validate_without_lost_updates() {
+    const THREADS: usize = 8;
+    const SPAWNS: usize = 50;
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            std::thread::spawn(|| {
+                let mut app = App::new();
+                app.insert_resource(PanicBehavior::Custom(record_concurrent));
+                app.init_resource::<ValidationStats>();
+                app.add_plugins(ExpectedComponentsPlugin);
+
+                for _ in 0..SPAWNS {
+                    app.world_mut().spawn(ConcurrentProbe);
+                    assert!(app
+                        .world_mut()
+                        .spawn_checked((ConcurrentProbe, Position))
+                        .is_ok());
+                }
+
+                validation_stats_by_type(app.world())
+                    .into_iter()
+                    .find(|(name, _)| name.ends_with("ConcurrentProbe"))
+                    .map(|(_, stats)| stats.validations)
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Some(2 * SPAWNS as u64));
+    }
+    assert_eq!(CONCURRENT_MESSAGES.lock().unwrap().len(), THREADS * SPAWNS);
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position, tags = ["physics"])]
+#[expects(Velocity, tags = ["movement", "debug"])]
+struct TaggedBody;
+
+fn app_with_tags(tags: &[&str]) -> App {
+    let mut app = App::new();
+    app.insert_resource(EnabledTags::new(tags.iter().copied()));
+    app.add_plugins(ExpectedComponentsPlugin);
+    app
+}
+
+#[test]
+fn enabled_tags_skip_expectations_without_enabled_tag() {
+    let mut app = app_with_tags(&["physics"]);
+
+    app.world_mut().spawn((TaggedBody, Position));
+    app.world_mut().spawn((SingleExpectation, Velocity));
+}
+
+#[test]
+#[should_panic(expected = "TaggedBody expects integration::Position")]
+fn enabled_tags_enforce_expectations_with_enabled_tag() {
+    let mut app = app_with_tags(&["physics"]);
+
+    app.world_mut().spawn((TaggedBody, Velocity));
+}
+
+#[test]
+#[should_panic(expected = "TaggedBody expects integration::Velocity")]
+fn enabled_tags_match_any_tag_of_expectation() {
+    let mut app = app_with_tags(&["debug"]);
+
+    app.world_mut().spawn((TaggedBody, Position));
+}
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position, Velocity, group = "physics")]
+struct GroupedBody;
+
+#[test]
+fn disabled_group_skips_its_expectations() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    app.world_mut()
+        .resource_mut::<ExpectationGroups>()
+        .disable_group("physics");
+
+    app.world_mut().spawn(GroupedBody);
+}
+
+#[test]
+#[should_panic(expected = "GroupedBody expects integration::Position, integration::Velocity")]
+fn re_enabled_group_is_enforced_again() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    let mut groups = app.world_mut().resource_mut::<ExpectationGroups>();
+    groups.disable_group("physics");
+    groups.enable_group("physics");
+
+    app.world_mut().spawn(GroupedBody);
+}
+
+#[test]
+#[should_panic(expected = "TaggedBody expects integration::Position")]
+fn disabling_a_group_leaves_other_groups_enforced() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    app.world_mut()
+        .resource_mut::<ExpectationGroups>()
+        .disable_group("movement");
+
+    app.world_mut().spawn((TaggedBody, Position));
+    app.world_mut().spawn((TaggedBody, Velocity));
+}
+
+static REGISTRY_MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_registry(message: &str) {
+    REGISTRY_MESSAGES.lock().unwrap().push(message.to_owned());
+}
+
+fn failures_with_registry(registry: ExpectationRegistry) -> Vec<String> {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Custom(record_registry));
+    app.add_plugins(ExpectedComponentsPlugin.with_registry(registry));
+
+    app.world_mut().spawn((PhysicsBody, Velocity));
+    app.world_mut().spawn(SingleExpectation);
+    app.update();
+
+    REGISTRY_MESSAGES.lock().unwrap().drain(..).collect()
+}
+
+#[test]
+fn registries_enforce_only_their_expectations() {
+    let physics = failures_with_registry(ExpectationRegistry::new().with::<PhysicsBody>());
+    assert_eq!(physics.len(), 1);
+    assert!(physics[0].contains("PhysicsBody expects"));
+
+    let single = failures_with_registry(ExpectationRegistry::new().with::<SingleExpectation>());
+    assert_eq!(single.len(), 1);
+    assert!(single[0].contains("SingleExpectation expects"));
+}
+
+/// Registered only on the plugin, never collected by `inventory`.
+#[derive(Component)]
+struct RegistryOnly;
+
+impl ExpectComponents for RegistryOnly {
+    const EXPECTED: &'static [TypeId] = &[TypeId::of::<Position>()];
+    const EXPECTED_NAMES: &'static [&'static str] = &["integration::Position"];
+
+    fn expected_modes() -> &'static [(&'static str, FailureMode)] {
+        const MODES: &[(&str, FailureMode)] =
+            &[("integration::Position", FailureMode::RemoveComponent)];
+        MODES
+    }
+}
+
+#[test]
+fn registry_only_type_keeps_its_expectation_mode() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin.register::<RegistryOnly>());
+
+    let entity = app.world_mut().spawn((RegistryOnly, Velocity)).id();
+    app.update();
+
+    assert!(!app.world().entity(entity).contains::<RegistryOnly>());
+    assert!(app.world().entity(entity).contains::<Velocity>());
+}
+
+#[test]
+fn registry_only_type_is_covered_by_world_validation() {
+    let mut app = App::new();
+    app.add_plugins(
+        ExpectedComponentsPlugin.with_registry(ExpectationRegistry::new().with::<RegistryOnly>()),
+    );
+
+    let complete = app.world_mut().spawn((RegistryOnly, Position)).id();
+    assert!(validate_entity(app.world(), complete).is_ok());
+
+    let world = app.world_mut();
+    let violations = world.spawn_validated(RegistryOnly).unwrap_err();
+    assert_eq!(violations[0].expecting, "integration::RegistryOnly");
+    let report = world.spawn_checked(RegistryOnly).err().unwrap();
+    assert_eq!(report.violations[0].missing, "integration::Position");
+
+    // Without a world, only collected types are known
+    assert!(validate_bundle::<RegistryOnly>().is_ok());
+}
+
+#[derive(Resource)]
+struct DevMode;
+
+static CONDITIONAL_MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_conditional(message: &str) {
+    CONDITIONAL_MESSAGES
+        .lock()
+        .unwrap()
+        .push(message.to_owned());
+}
+
+#[test]
+fn validate_if_runs_pass_only_while_condition_holds() {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Custom(record_conditional));
+    app.add_plugins(
+        ExpectedComponentsPlugin
+            .validate_after_flush(Last)
+            .validate_if(resource_exists::<DevMode>),
+    );
+
+    app.world_mut().spawn(SingleExpectation);
+    app.update();
+    assert!(CONDITIONAL_MESSAGES.lock().unwrap().is_empty());
+
+    app.insert_resource(DevMode);
+    app.update();
+    assert_eq!(CONDITIONAL_MESSAGES.lock().unwrap().len(), 1);
+}
+
+static PRESENT_MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_present(message: &str) {
+    PRESENT_MESSAGES.lock().unwrap().push(message.to_owned());
+}
+
+#[test]
+fn failure_message_lists_present_components() {
+    let mut app = App::new();
+    app.insert_resource(PanicBehavior::Custom(record_present));
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let entity = app.world_mut().spawn((PhysicsBody, Position)).id();
+
+    let messages = PRESENT_MESSAGES.lock().unwrap();
+    assert_eq!(messages.len(), 1);
+    let present = messages[0].lines().nth(1).unwrap();
+    assert!(present.starts_with(&format!("entity {entity:?} currently has: ")));
+    assert!(present.contains(std::any::type_name::<PhysicsBody>()));
+    assert!(present.contains(std::any::type_name::<Position>()));
+}
+
+fn insert_body(mut commands: Commands) {
+    commands.spawn(PhysicsBody);
+}
+
+fn finish_bodies(
+    mut commands: Commands,
+    bodies: Query<Entity, (With<PhysicsBody>, Without<Velocity>)>,
+) {
+    for entity in &bodies {
+        commands.entity(entity).insert((Position, Velocity));
+    }
+}
+
+#[test]
+fn deferred_allows_assembly_across_systems_within_frame() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin::deferred());
+    app.add_systems(Update, (insert_body, finish_bodies).chain());
+
+    app.update();
+}
+
+#[test]
+#[should_panic(expected = "PhysicsBody expects")]
+fn deferred_reports_entities_left_incomplete_at_end_of_frame() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin::deferred());
+    app.add_systems(Update, insert_body);
+
+    app.update();
+}
+
+#[test]
+fn suppressed_types_are_not_validated() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    app.world_mut()
+        .get_resource_or_init::<ExpectationOverrides>()
+        .suppress::<PhysicsBody>();
+
+    app.world_mut().spawn(PhysicsBody);
+    app.world_mut().spawn((SingleExpectation, Position));
+}
+
+#[test]
+#[should_panic(expected = "PhysicsBody expects")]
+fn unsuppressed_types_are_validated_again() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    let mut overrides = app
+        .world_mut()
+        .get_resource_or_init::<ExpectationOverrides>();
+    overrides.suppress::<PhysicsBody>();
+    overrides.unsuppress::<PhysicsBody>();
+
+    app.world_mut().spawn(PhysicsBody);
+}
+
+#[test]
+#[should_panic(expected = "SingleExpectation expects")]
+fn suppression_leaves_other_types_validated() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+    app.world_mut()
+        .get_resource_or_init::<ExpectationOverrides>()
+        .suppress::<PhysicsBody>();
+
+    app.world_mut().spawn(SingleExpectation);
+}
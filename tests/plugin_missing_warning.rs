@@ -0,0 +1,75 @@
+//! Lives in its own test binary because it installs a global logger to capture
+//! the warning.
+#![cfg(feature = "validate")]
+
+use std::sync::Mutex;
+use std::thread;
+
+use bevy_app::App;
+use bevy_ecs::component::Component;
+use bevy_ecs::world::World;
+use bevy_expected_components::plugin_installed;
+use bevy_expected_components::prelude::*;
+
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct CaptureLogger;
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            WARNINGS.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger;
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct Lonely;
+
+fn warnings_about_lonely() -> usize {
+    WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|warning| warning.contains("Lonely") && warning.contains("was not added"))
+        .count()
+}
+
+#[test]
+fn warns_once_when_plugin_missing_then_not_when_installed() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+
+    let mut world = World::new();
+    let entity = world.spawn((Lonely, Position)).id();
+
+    assert!(!plugin_installed());
+    debug_assert_lonely_expectations(&world.entity(entity));
+    debug_assert_lonely_expectations(&world.entity(entity));
+    assert_eq!(warnings_about_lonely(), 1);
+
+    // Systems run on task pool threads, so a plugin built on one thread counts
+    // on every other
+    thread::spawn(|| {
+        let mut app = App::new();
+        app.add_plugins(ExpectedComponentsPlugin);
+    })
+    .join()
+    .unwrap();
+
+    assert!(plugin_installed());
+    debug_assert_lonely_expectations(&world.entity(entity));
+    assert_eq!(warnings_about_lonely(), 1);
+}
@@ -0,0 +1,90 @@
+//! Lives in its own test binary because it installs a global logger to count
+//! the warnings.
+#![cfg(feature = "validate")]
+
+use std::sync::Mutex;
+
+use bevy_app::App;
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::ViolationRateLimit;
+
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct CaptureLogger;
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            WARNINGS.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger;
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component)]
+struct Velocity;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct Npc;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Velocity)]
+struct Projectile;
+
+fn warnings_containing(text: &str) -> usize {
+    WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|warning| warning.contains(text))
+        .count()
+}
+
+#[test]
+fn rate_limit_caps_warnings_per_frame() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Warn);
+    app.insert_resource(ViolationRateLimit(2));
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    for _ in 0..5 {
+        app.world_mut().spawn(Npc);
+    }
+    assert_eq!(warnings_containing("Npc expects"), 2);
+
+    app.update();
+    assert_eq!(
+        warnings_containing("3 more expectation failures were not logged this frame"),
+        1
+    );
+
+    app.world_mut().spawn(Npc);
+    assert_eq!(warnings_containing("Npc expects"), 3);
+
+    // A pattern suppressed under WarnOnce is still warned about on a later frame
+    app.world_mut().spawn(Npc);
+    app.insert_resource(FailureMode::WarnOnce);
+    app.world_mut().spawn(Projectile);
+    assert_eq!(warnings_containing("Npc expects"), 4);
+    assert_eq!(warnings_containing("Projectile expects"), 0);
+
+    app.update();
+    app.world_mut().spawn(Projectile);
+    app.world_mut().spawn(Projectile);
+    assert_eq!(warnings_containing("Projectile expects"), 1);
+}
@@ -0,0 +1,77 @@
+//! Lives in its own test binary because registering types without `inventory`
+//! changes process-wide state that other tests would observe.
+#![cfg(feature = "validate")]
+
+use std::any::TypeId;
+
+use bevy_app::App;
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::{
+    all_registrations, is_registered, register_all_expectations, ValidationReport,
+};
+
+#[derive(Component)]
+struct Position;
+
+/// Never submitted to `inventory`, like every type on a platform where it
+/// collects nothing.
+#[derive(Component)]
+struct Beacon;
+
+impl ExpectComponents for Beacon {
+    const EXPECTED: &'static [TypeId] = &[TypeId::of::<Position>()];
+    const EXPECTED_NAMES: &'static [&'static str] = &["register_all::Position"];
+}
+
+/// What `#[derive(ExpectComponents)]` generates for `Beacon`.
+#[allow(non_snake_case)]
+fn __register_Beacon() {
+    bevy_expected_components::__submit_registration::<Beacon>();
+}
+
+mod units {
+    use bevy_ecs::component::Component;
+    use bevy_expected_components::prelude::*;
+
+    #[derive(Component, ExpectComponents)]
+    #[expects(super::Position)]
+    pub struct Scout;
+}
+
+fn registrations_of(type_name: &str) -> usize {
+    all_registrations()
+        .filter(|info| info.type_name == type_name)
+        .count()
+}
+
+#[test]
+fn register_all_expectations_registers_types_inventory_missed() {
+    assert!(!is_registered::<Beacon>());
+
+    register_all_expectations!(Beacon, units::Scout);
+    register_all_expectations!(Beacon);
+
+    assert!(is_registered::<Beacon>());
+    assert_eq!(registrations_of("register_all::Beacon"), 1);
+    assert_eq!(registrations_of("register_all::units::Scout"), 1);
+
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((Beacon, Position));
+    app.world_mut().spawn(Beacon);
+    app.world_mut().spawn(units::Scout);
+
+    let report = app.world().resource::<ValidationReport>();
+    let expecting: Vec<_> = report
+        .violations
+        .iter()
+        .map(|violation| violation.expecting)
+        .collect();
+    assert_eq!(
+        expecting,
+        ["register_all::Beacon", "register_all::units::Scout"]
+    );
+}
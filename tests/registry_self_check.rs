@@ -0,0 +1,42 @@
+//! Lives in its own test binary because the malformed registration below would
+//! make every plugin build in the main integration tests panic.
+#![cfg(feature = "validate")]
+
+use std::any::TypeId;
+
+use bevy_app::App;
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::ExpectRegistration;
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component)]
+struct Velocity;
+
+#[derive(Component)]
+struct Malformed;
+
+impl ExpectComponents for Malformed {
+    const EXPECTED: &'static [TypeId] = &[TypeId::of::<Position>(), TypeId::of::<Velocity>()];
+    const EXPECTED_NAMES: &'static [&'static str] = &["Position"];
+}
+
+bevy_expected_components::inventory::submit!(ExpectRegistration::of::<Malformed>());
+
+#[test]
+fn find_inconsistency_reports_mismatched_lengths() {
+    let registration = ExpectRegistration::of::<Malformed>();
+
+    let problem = registration.find_inconsistency().unwrap();
+    assert!(problem.contains("expected_components"));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Malformed has inconsistent expectation metadata")]
+fn plugin_build_panics_on_inconsistent_registration() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+}
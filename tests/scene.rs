@@ -0,0 +1,85 @@
+//! Needs the `scene` feature, so it lives in its own test binary.
+#![cfg(feature = "scene")]
+
+use bevy_app::{App, TaskPoolPlugin};
+use bevy_asset::{AssetPlugin, Assets};
+use bevy_ecs::component::Component;
+use bevy_ecs::hierarchy::Children;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_ecs::world::World;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::{SceneValidationReport, ValidationEnabled, ValidationReport};
+use bevy_reflect::Reflect;
+use bevy_scene::{Scene, ScenePlugin, SceneRoot};
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+struct Position;
+
+#[derive(Component, ExpectComponents, Reflect, Default)]
+#[reflect(Component)]
+#[expects(Position)]
+struct Enemy;
+
+fn app_with_scene(entities: impl FnOnce(&mut World)) -> (App, bevy_ecs::entity::Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        AssetPlugin::default(),
+        ScenePlugin,
+    ));
+    app.register_type::<Position>().register_type::<Enemy>();
+    app.add_plugins(ExpectedComponentsPlugin.batch_scene_validation());
+
+    let mut scene_world = World::new();
+    entities(&mut scene_world);
+    let scene = app
+        .world_mut()
+        .resource_mut::<Assets<Scene>>()
+        .add(Scene::new(scene_world));
+
+    let root = app.world_mut().spawn(SceneRoot(scene)).id();
+    app.update();
+    (app, root)
+}
+
+#[test]
+fn invalid_scene_entities_are_reported_without_panicking() {
+    let (app, root) = app_with_scene(|world| {
+        world.spawn(Enemy);
+        world.spawn(Enemy);
+        world.spawn((Enemy, Position));
+    });
+
+    let children = app.world().get::<Children>(root).unwrap();
+    assert_eq!(children.len(), 3);
+
+    let report = app.world().resource::<SceneValidationReport>();
+    assert_eq!(report.entities.len(), 2);
+    for violations in report.entities.values() {
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].missing, "scene::Position");
+    }
+}
+
+#[test]
+fn valid_scene_leaves_report_empty() {
+    let (app, _) = app_with_scene(|world| {
+        world.spawn((Enemy, Position));
+    });
+
+    assert!(app.world().resource::<SceneValidationReport>().is_empty());
+}
+
+#[test]
+fn validation_is_resumed_after_the_scene_spawner() {
+    let (mut app, _) = app_with_scene(|_| {});
+
+    assert!(app.world().get_resource::<ValidationEnabled>().is_none());
+    app.insert_resource(FailureMode::Collect);
+    app.world_mut().spawn(Enemy);
+    assert_eq!(
+        app.world().resource::<ValidationReport>().violations.len(),
+        1
+    );
+}
@@ -0,0 +1,61 @@
+//! Needs the `state` feature, so it lives in its own test binary.
+#![cfg(feature = "state")]
+
+use bevy_app::App;
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::ValidationReport;
+use bevy_state::app::{AppExtStates, StatesPlugin};
+use bevy_state::state::{NextState, States};
+
+#[derive(States, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+enum AppState {
+    #[default]
+    Loading,
+    InGame,
+}
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct Enemy;
+
+fn app_validating_in_game() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatesPlugin);
+    app.init_state::<AppState>();
+    app.add_plugins(
+        ExpectedComponentsPlugin
+            .with_failure_mode(FailureMode::Collect)
+            .run_in_state(AppState::InGame),
+    );
+    app
+}
+
+fn violation_count(app: &App) -> usize {
+    app.world().resource::<ValidationReport>().violations.len()
+}
+
+#[test]
+fn validation_is_skipped_outside_the_state() {
+    let mut app = app_validating_in_game();
+
+    app.world_mut().spawn(Enemy);
+    app.update();
+    assert_eq!(violation_count(&app), 0);
+}
+
+#[test]
+fn validation_runs_once_the_state_is_entered() {
+    let mut app = app_validating_in_game();
+
+    app.world_mut().spawn(Enemy);
+    app.update();
+    app.world_mut()
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::InGame);
+    app.update();
+    assert_eq!(violation_count(&app), 1);
+}
@@ -0,0 +1,53 @@
+//! Needs the `test-utils` feature, so it lives in its own test binary.
+#![cfg(feature = "test-utils")]
+
+use bevy_ecs::component::Component;
+use bevy_ecs::world::World;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::{assert_expectation_violated, assert_expectations_satisfied};
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component)]
+struct Velocity;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position, Velocity)]
+struct PhysicsBody;
+
+#[test]
+fn satisfied_entity_passes() {
+    let mut world = World::new();
+    let entity = world.spawn((PhysicsBody, Position, Velocity)).id();
+
+    assert_expectations_satisfied(&world, entity);
+}
+
+#[test]
+#[should_panic(
+    expected = "missing:\n    - test_utils::Velocity, expected by test_utils::PhysicsBody\n  present:\n    + test_utils::PhysicsBody\n    + test_utils::Position"
+)]
+fn unsatisfied_entity_lists_missing_and_present_components() {
+    let mut world = World::new();
+    let entity = world.spawn((PhysicsBody, Position)).id();
+
+    assert_expectations_satisfied(&world, entity);
+}
+
+#[test]
+fn violated_expectation_passes() {
+    let mut world = World::new();
+    let entity = world.spawn((PhysicsBody, Position)).id();
+
+    assert_expectation_violated::<PhysicsBody>(&world, entity);
+}
+
+#[test]
+#[should_panic(expected = "but it satisfies them")]
+fn satisfied_expectation_fails_violation_assertion() {
+    let mut world = World::new();
+    let entity = world.spawn((PhysicsBody, Position, Velocity)).id();
+
+    assert_expectation_violated::<PhysicsBody>(&world, entity);
+}
@@ -0,0 +1,60 @@
+//! Needs the `track_location` feature, which records the insert location on
+//! every hook failure, so it lives in its own test binary.
+#![cfg(feature = "track_location")]
+
+use bevy_app::App;
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::ValidationReport;
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct Enemy;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position, message = "spawn {expecting} with a {missing}")]
+struct Turret;
+
+#[test]
+fn violation_records_insert_location() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    let line = line!() + 1;
+    let entity = app.world_mut().spawn(Enemy).id();
+
+    let violation = &app.world().resource::<ValidationReport>().violations[0];
+    let caller = violation.caller.unwrap();
+    assert_eq!(
+        (caller.file(), caller.line()),
+        ("tests/track_location.rs", line)
+    );
+    assert_eq!(
+        violation.message.lines().next().unwrap(),
+        format!("track_location::Enemy expects track_location::Position but it was not found on entity {entity}")
+    );
+    assert!(violation
+        .to_string()
+        .ends_with(&format!("\ninserted at {caller}")));
+}
+
+#[test]
+fn custom_message_is_left_untouched() {
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Collect);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(Turret);
+
+    let violation = &app.world().resource::<ValidationReport>().violations[0];
+    assert!(violation.caller.is_some());
+    let message = violation.message.lines().next().unwrap();
+    assert!(
+        message.ends_with(": spawn track_location::Turret with a track_location::Position"),
+        "{message}"
+    );
+}
@@ -0,0 +1,5 @@
+use bevy_expected_components::register_all_expectations;
+
+fn main() {
+    register_all_expectations!(Wrapper<u8>);
+}
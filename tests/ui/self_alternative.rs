@@ -0,0 +1,11 @@
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+
+#[derive(Component)]
+struct Sprite;
+
+#[derive(Component, ExpectComponents)]
+#[expect_any(Sprite, Mesh2d)]
+struct Mesh2d;
+
+fn main() {}
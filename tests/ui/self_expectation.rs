@@ -0,0 +1,8 @@
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+
+#[derive(Component, ExpectComponents)]
+#[expects(PhysicsBody)]
+struct PhysicsBody;
+
+fn main() {}
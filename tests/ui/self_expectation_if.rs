@@ -0,0 +1,11 @@
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+
+#[derive(Component)]
+struct PhysicsEnabled;
+
+#[derive(Component, ExpectComponents)]
+#[expect_if(PhysicsEnabled, Self)]
+struct RigidBody;
+
+fn main() {}
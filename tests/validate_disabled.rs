@@ -0,0 +1,30 @@
+//! Checks that disabling the `validate` feature turns validation off entirely.
+#![cfg(not(feature = "validate"))]
+
+use bevy_app::App;
+use bevy_ecs::component::Component;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::{is_registered, plugin_installed};
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+struct Enemy;
+
+#[test]
+fn derive_registers_nothing() {
+    assert!(!is_registered::<Enemy>());
+}
+
+#[test]
+fn plugin_does_nothing() {
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn(Enemy);
+    app.update();
+
+    assert!(!plugin_installed());
+}
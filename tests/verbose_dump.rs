@@ -0,0 +1,108 @@
+//! Lives in its own test binary because it installs a global logger to capture
+//! the dump.
+#![cfg(feature = "validate")]
+
+use std::sync::Mutex;
+
+use bevy_app::App;
+use bevy_ecs::component::Component;
+use bevy_ecs::name::Name;
+use bevy_expected_components::prelude::*;
+use bevy_expected_components::{FailureBacktraces, VerboseFailures};
+
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct CaptureLogger;
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            WARNINGS.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger;
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component)]
+struct Velocity;
+
+#[derive(Component)]
+struct Mass;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position, Velocity)]
+struct PhysicsBody;
+
+fn dumps() -> Vec<String> {
+    WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|warning| warning.starts_with("components of entity"))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn verbose_failures_dump_present_components() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+
+    let mut app = App::new();
+    app.insert_resource(FailureMode::Warn);
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    app.world_mut().spawn((PhysicsBody, Position));
+    assert!(dumps().is_empty());
+
+    app.insert_resource(VerboseFailures);
+    let entity = app.world_mut().spawn((PhysicsBody, Position, Mass)).id();
+
+    let dumps = dumps();
+    assert_eq!(dumps.len(), 1);
+    assert!(dumps[0].starts_with(&format!(
+        "components of entity {entity:?} (index {}, generation 0, one of 1 entities in its archetype):",
+        entity.index_u32()
+    )));
+    for name in ["PhysicsBody", "Position", "Mass"] {
+        assert!(
+            dumps[0].contains(&format!("verbose_dump::{name}")),
+            "{name} missing from {}",
+            dumps[0]
+        );
+    }
+    assert!(!dumps[0].contains("Velocity"));
+
+    let named = app
+        .world_mut()
+        .spawn((PhysicsBody, Position, Name::new("Crate")))
+        .id();
+    assert!(last_dump().starts_with(&format!("components of entity \"Crate\" {named:?} (")));
+
+    assert!(!warned("backtrace of the failed expectation"));
+    app.insert_resource(FailureBacktraces);
+    app.world_mut().spawn((PhysicsBody, Position));
+    assert!(warned("backtrace of the failed expectation"));
+}
+
+fn last_dump() -> String {
+    dumps().pop().unwrap()
+}
+
+fn warned(prefix: &str) -> bool {
+    WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|warning| warning.starts_with(prefix))
+}
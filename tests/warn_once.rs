@@ -0,0 +1,74 @@
+//! Lives in its own test binary because it installs a global logger to count
+//! the warnings.
+#![cfg(feature = "validate")]
+
+use std::sync::Mutex;
+
+use bevy_app::App;
+use bevy_ecs::component::Component;
+use bevy_expected_components::clear_warn_cache;
+use bevy_expected_components::prelude::*;
+
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct CaptureLogger;
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            WARNINGS.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger;
+
+#[derive(Component)]
+struct Position;
+
+#[derive(Component)]
+struct Name;
+
+#[derive(Component, ExpectComponents)]
+#[expects(Position)]
+#[expects(Name, mode = "warn_once")]
+struct Npc;
+
+fn warnings_about(expected: &str) -> usize {
+    WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|warning| warning.contains(&format!("Npc expects warn_once::{expected}")))
+        .count()
+}
+
+#[test]
+fn warn_once_logs_each_expectation_once_until_cache_cleared() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+
+    let mut app = App::new();
+    app.add_plugins(ExpectedComponentsPlugin);
+
+    for _ in 0..3 {
+        app.world_mut().spawn((Npc, Position));
+    }
+    assert_eq!(warnings_about("Name"), 1);
+
+    clear_warn_cache(app.world_mut());
+    app.world_mut().spawn((Npc, Position));
+    assert_eq!(warnings_about("Name"), 2);
+
+    // The global mode applies to expectations without their own
+    app.insert_resource(FailureMode::WarnOnce);
+    app.world_mut().spawn((Npc, Name));
+    app.world_mut().spawn((Npc, Name));
+    assert_eq!(warnings_about("Position"), 1);
+}